@@ -0,0 +1,70 @@
+use super::var::Var;
+use super::{Info, Node, NodeType, Value};
+use crate::data_type::POINTER;
+use crate::error::Error;
+use crate::global_scope::{Identifier, ReferenceSymbol, Scope};
+use std::{cell::RefCell, rc::Rc};
+
+/// AddressOf is `@designator`, an untyped pointer to the variable or its component. The variable
+/// is not moved to the heap, the heap holds a reference to it instead.
+pub struct AddressOf {
+    variable: Rc<Var>,
+}
+
+impl AddressOf {
+    pub fn new(variable: Rc<Var>) -> AddressOf {
+        AddressOf { variable }
+    }
+}
+
+impl Node for AddressOf {
+    fn r#type(&self) -> NodeType {
+        NodeType::AddressOf
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let name = match self.variable.name() {
+            Ok(Some(name)) => name,
+            _ => return Err(Error::VarNotFound),
+        };
+
+        match scope.borrow().get(&name) {
+            Some(Identifier::Variable(_)) => {}
+            Some(_) => {
+                eprintln!(
+                    "[visit] [{}] address of '{}' can not be taken, it is not a variable",
+                    self.r#type().as_str(),
+                    name
+                );
+                return Err(Error::VariableRequired);
+            }
+            None => return Err(Error::VarNotFound),
+        };
+
+        let selections = match self.variable.selections(scope.clone()) {
+            Ok(_) if scope.borrow().halted() => {
+                return Ok(Info::new(None, NodeType::AddressOf, None))
+            }
+            Ok(s) => s,
+            Err(e) => return Err(e),
+        };
+
+        let target = match Scope::defining_scope(scope.clone(), &name) {
+            Some(s) => s,
+            None => return Err(Error::VarNotFound),
+        };
+
+        let heap = scope.borrow().heap();
+        let address = heap
+            .borrow_mut()
+            .allocate(Identifier::Reference(ReferenceSymbol::new(
+                target, &name, selections,
+            )));
+
+        Ok(Info::new(
+            None,
+            NodeType::AddressOf,
+            Some(Value::new(POINTER, &address.to_string())),
+        ))
+    }
+}
@@ -1,21 +1,56 @@
-use super::{Info, Node, NodeType};
+use super::procedure::RETURN_VALUE;
+use super::var::Var;
+use super::{evaluate, Info, Node, NodeType};
 use crate::{
     error::Error,
-    global_scope::{Identifier, Scope, VariableSymbol},
-    lexer::lexeme::number::NumberType,
+    global_scope::{Heap, Identifier, Scope, VariableSymbol},
 };
 use std::{cell::RefCell, rc::Rc};
 
 pub struct Assign {
-    left: String,
+    left: Rc<Var>,
     right: Rc<dyn Node>,
 }
 
 impl Assign {
-    pub fn new(left: &str, right: Rc<dyn Node>) -> Assign {
-        Assign {
-            left: left.to_string(),
-            right,
+    pub fn new(left: Rc<Var>, right: Rc<dyn Node>) -> Assign {
+        Assign { left, right }
+    }
+}
+
+impl Assign {
+    /// target returns the scope and the name of the variable to assign, an assignment to the
+    /// name of a function sets the result of its active call.
+    fn target(&self, scope: Rc<RefCell<Scope>>) -> Result<(Rc<RefCell<Scope>>, String), Error> {
+        let left = match self.left.name() {
+            Ok(Some(name)) => name,
+            _ => return Err(Error::VarNotFound),
+        };
+
+        let ps = match scope.borrow().get(&left) {
+            Some(Identifier::Procedure(ps)) => ps,
+            _ => return Ok((scope.clone(), left)),
+        };
+
+        if ps.procedure().return_type().is_none() {
+            eprintln!(
+                "[visit] [{}] procedure '{}' can not be assigned",
+                self.r#type().as_str(),
+                left
+            );
+            return Err(Error::InvalidSyntax);
+        }
+
+        match Scope::lookup_scope(scope, &left) {
+            Some(s) => Ok((s, RETURN_VALUE.to_string())),
+            None => {
+                eprintln!(
+                    "[visit] [{}] result of function '{}' can only be assigned inside the function",
+                    self.r#type().as_str(),
+                    left
+                );
+                Err(Error::InvalidSyntax)
+            }
         }
     }
 }
@@ -26,31 +61,60 @@ impl Node for Assign {
     }
 
     fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
-        match self.right.visit(scope.clone()) {
-            Ok(info) => match info.value() {
-                Some(v) => {
-                    let r#type = match NumberType::to_number_type(&v.r#type) {
-                        Ok(t) => t,
-                        Err(e) => return Err(e),
-                    };
-                    if let Err(e) = scope.borrow_mut().set(
-                        &self.left,
-                        Identifier::Variable(VariableSymbol::new(r#type, Some(v.value))),
-                    ) {
-                        return Err(e);
-                    }
-                }
-                None => {
-                    println!(
-                        "[visit] [{}] error occurred, value not found in info",
-                        self.r#type().as_str()
-                    );
-                    return Err(Error::InvalidSyntax);
-                }
-            },
+        let v = match evaluate(&self.right, scope.clone()) {
+            Ok(Some(v)) => v,
+            /* The function computing the value halted the program, nothing is assigned */
+            Ok(None) => return Ok(Info::new(None, NodeType::Assign, None)),
+            Err(e) => return Err(e),
+        };
+
+        let (target_scope, name) = match self.target(scope.clone()) {
+            Ok(t) => t,
+            Err(e) => return Err(e),
+        };
+        let selections = match self.left.selections(scope.clone()) {
+            Ok(_) if scope.borrow().halted() => return Ok(Info::new(None, NodeType::Assign, None)),
+            Ok(s) => s,
             Err(e) => return Err(e),
         };
 
+        let vs = match target_scope.borrow().get(&name) {
+            Some(Identifier::Variable(vs)) => vs,
+            Some(Identifier::Constant(_)) => {
+                eprintln!(
+                    "[visit] [{}] constant '{}' can not be assigned",
+                    self.r#type().as_str(),
+                    name
+                );
+                return Err(Error::ConstantAssigned);
+            }
+            _ => {
+                eprintln!(
+                    "[visit] [{}] variable '{}' not found",
+                    self.r#type().as_str(),
+                    name
+                );
+                return Err(Error::VarNotFound);
+            }
+        };
+
+        /* The value is converted to the declared type of the variable or component */
+        let heap = target_scope.borrow().heap();
+        let val = match Heap::store(&heap, &vs, &selections, v) {
+            Ok(Some(val)) => val,
+            /* The component reached through a pointer is written to the heap */
+            Ok(None) => return Ok(Info::new(None, NodeType::Assign, None)),
+            Err(e) => return Err(e),
+        };
+
+        let r = target_scope.borrow_mut().set(
+            &name,
+            Identifier::Variable(VariableSymbol::new(vs.r#type(), Some(val))),
+        );
+        if let Err(e) = r {
+            return Err(e);
+        }
+
         Ok(Info::new(None, NodeType::Assign, None))
     }
 }
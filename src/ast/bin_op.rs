@@ -1,4 +1,5 @@
-use super::{Info, Node, NodeType, Value};
+use super::{evaluate, Info, Node, NodeType, Value};
+use crate::data_type::{DataType, EMPTY_SET, POINTER};
 use crate::error::Error;
 use crate::global_scope::Scope;
 use crate::lexer::lexeme::number::NumberType;
@@ -28,6 +29,8 @@ where
         + Mul<Output = T>
         + Div<Output = T>
         + std::fmt::Display
+        + Default
+        + PartialEq
         + Copy,
     <T as FromStr>::Err: std::fmt::Display,
 {
@@ -36,7 +39,7 @@ where
         match n.parse::<T>() {
             Ok(num) => nums.push(num),
             Err(e) => {
-                println!("[visit] [BinOp] parse num '{}' failed, error: {}", left, e);
+                eprintln!("[visit] [BinOp] parse num '{}' failed, error: {}", n, e);
                 return Err(Error::InvalidSyntax);
             }
         };
@@ -50,15 +53,175 @@ where
     } else if Op::Mul.equal_type(r#type) {
         r = nums[0] * nums[1];
     } else if Op::Div.equal_type(r#type) {
+        if nums[1] == T::default() {
+            eprintln!("[visit] [BinOp] '{}' is divided by zero", left);
+            return Err(Error::DivisionByZero);
+        }
         r = nums[0] / nums[1];
     } else {
-        println!("[visit] [BinOp] invalid op {}", r#type);
+        eprintln!("[visit] [BinOp] invalid op {}", r#type);
         return Result::Err(Error::InvalidSyntax);
     }
 
     Ok(r)
 }
 
+/// int_div calculates DIV and MOD, both truncate toward zero, so the result of MOD has the
+/// sign of the dividend, e.g. -7 DIV 2 = -3 and -7 MOD 2 = -1.
+fn int_div(left: &str, r#type: &str, right: &str) -> Result<i32, Error> {
+    let mut nums: Vec<i32> = Vec::new();
+    for n in [left, right] {
+        match n.parse::<i32>() {
+            Ok(num) => nums.push(num),
+            Err(e) => {
+                eprintln!("[visit] [BinOp] parse num '{}' failed, error: {}", n, e);
+                return Err(Error::InvalidSyntax);
+            }
+        };
+    }
+
+    if nums[1] == 0 {
+        eprintln!("[visit] [BinOp] '{}' is divided by zero", left);
+        return Err(Error::DivisionByZero);
+    }
+
+    if Op::IntDiv.equal_type(r#type) {
+        Ok(nums[0].wrapping_div(nums[1]))
+    } else if Op::Mod.equal_type(r#type) {
+        Ok(nums[0].wrapping_rem(nums[1]))
+    } else {
+        eprintln!("[visit] [BinOp] invalid op {}", r#type);
+        Err(Error::InvalidSyntax)
+    }
+}
+
+fn compare<T>(left: &str, r#type: &str, right: &str) -> Result<bool, Error>
+where
+    T: FromStr + PartialOrd,
+    <T as FromStr>::Err: std::fmt::Display,
+{
+    let mut vals: Vec<T> = Vec::new();
+    for v in [left, right] {
+        match v.parse::<T>() {
+            Ok(val) => vals.push(val),
+            Err(e) => {
+                eprintln!("[visit] [BinOp] parse value '{}' failed, error: {}", v, e);
+                return Err(Error::InvalidSyntax);
+            }
+        };
+    }
+
+    let r: bool;
+    if Op::Eq.equal_type(r#type) {
+        r = vals[0] == vals[1];
+    } else if Op::NotEq.equal_type(r#type) {
+        r = vals[0] != vals[1];
+    } else if Op::Lt.equal_type(r#type) {
+        r = vals[0] < vals[1];
+    } else if Op::Le.equal_type(r#type) {
+        r = vals[0] <= vals[1];
+    } else if Op::Gt.equal_type(r#type) {
+        r = vals[0] > vals[1];
+    } else if Op::Ge.equal_type(r#type) {
+        r = vals[0] >= vals[1];
+    } else {
+        eprintln!("[visit] [BinOp] invalid relational op {}", r#type);
+        return Result::Err(Error::InvalidSyntax);
+    }
+
+    Ok(r)
+}
+
+/// is_pointer reports whether the type of a value is a pointer type.
+fn is_pointer(r#type: &str) -> bool {
+    r#type == POINTER || r#type.starts_with('^')
+}
+
+/// is_set reports whether the type of a value is a set type.
+fn is_set(r#type: &str) -> bool {
+    r#type == EMPTY_SET || matches!(DataType::from_tag(r#type), Some(DataType::Set(_)))
+}
+
+impl BinOp {
+    /// set_op calculates the ops on sets, IN tests the membership of an element, '+', '*' and '-'
+    /// are the union, the intersection and the difference, '<=' and '>=' test for subsets.
+    fn set_op(&self, left: &Value, right: &Value) -> Result<Value, Error> {
+        let op = self.token.r#type();
+        let mismatch = || {
+            eprintln!(
+                "[visit] [{}] op '{}' can not be applied to '{}' and '{}'",
+                self.r#type().as_str(),
+                self.token.value(),
+                left.r#type,
+                right.r#type,
+            );
+            Err(Error::TypeMismatch)
+        };
+
+        if Op::In.equal_type(op) {
+            let member = match DataType::from_tag(&right.r#type) {
+                Some(DataType::Set(st)) if st.base().tag() == left.r#type => {
+                    match (left.ordinal(), right.ordinals()) {
+                        (Ok(n), Ok(ordinals)) => ordinals.contains(&n),
+                        (Err(e), _) | (_, Err(e)) => return Err(e),
+                    }
+                }
+                _ if right.r#type == EMPTY_SET && left.ordinal().is_ok() => false,
+                _ => return mismatch(),
+            };
+            return Ok(Value::new(
+                NumberType::Boolean.r#type(),
+                &member.to_string(),
+            ));
+        }
+
+        /* The empty set can be combined with a set of any type */
+        let r#type = if left.r#type == EMPTY_SET {
+            &right.r#type
+        } else {
+            &left.r#type
+        };
+        if !is_set(&left.r#type)
+            || !is_set(&right.r#type)
+            || (right.r#type != EMPTY_SET && right.r#type != *r#type)
+        {
+            return mismatch();
+        }
+
+        let (l, r) = match (left.ordinals(), right.ordinals()) {
+            (Ok(l), Ok(r)) => (l, r),
+            (Err(e), _) | (_, Err(e)) => return Err(e),
+        };
+
+        let r = if Op::Eq.equal_type(op) {
+            l == r
+        } else if Op::NotEq.equal_type(op) {
+            l != r
+        } else if Op::Le.equal_type(op) {
+            l.is_subset(&r)
+        } else if Op::Ge.equal_type(op) {
+            l.is_superset(&r)
+        } else {
+            let ordinals = if Op::Add.equal_type(op) {
+                l.union(&r).cloned().collect()
+            } else if Op::Mul.equal_type(op) {
+                l.intersection(&r).cloned().collect()
+            } else if Op::Sub.equal_type(op) {
+                l.difference(&r).cloned().collect()
+            } else {
+                return mismatch();
+            };
+
+            return match DataType::from_tag(r#type) {
+                Some(DataType::Set(st)) => st.value(&ordinals),
+                _ => Ok(Value::set(EMPTY_SET, Vec::new())),
+            };
+        };
+
+        Ok(Value::new(NumberType::Boolean.r#type(), &r.to_string()))
+    }
+}
+
 impl Node for BinOp {
     fn r#type(&self) -> NodeType {
         NodeType::BinOp
@@ -67,25 +230,175 @@ impl Node for BinOp {
     fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
         let mut vals: Vec<Value> = Vec::new();
         for n in [self.left.clone(), self.right.clone()] {
-            match n.visit(scope.clone()) {
-                Ok(info) => match info.value() {
-                    Some(v) => vals.push(v),
-                    None => {
-                        println!(
-                            "[visit] [{}] value not found in this node",
-                            self.r#type().as_str()
-                        );
-                        return Err(Error::InvalidSyntax);
-                    }
-                },
+            match evaluate(&n, scope.clone()) {
+                Ok(Some(v)) => vals.push(v),
+                Ok(None) => return Ok(Info::new(None, NodeType::BinOp, None)),
                 Err(e) => return Err(e),
             };
         }
 
+        if Op::In.equal_type(self.token.r#type()) || vals.iter().any(|v| is_set(v.r#type())) {
+            return match self.set_op(&vals[0], &vals[1]) {
+                Ok(val) => Ok(Info::new(None, NodeType::BinOp, Some(val))),
+                Err(e) => Err(e),
+            };
+        }
+
+        /* Pointers can only be compared for equality */
+        if vals.iter().any(|v| is_pointer(v.r#type())) {
+            if !is_pointer(&vals[0].r#type)
+                || !is_pointer(&vals[1].r#type)
+                || (vals[0].r#type != vals[1].r#type
+                    && vals[0].r#type != POINTER
+                    && vals[1].r#type != POINTER)
+                || !(Op::Eq.equal_type(self.token.r#type())
+                    || Op::NotEq.equal_type(self.token.r#type()))
+            {
+                eprintln!(
+                    "[visit] [{}] op '{}' can not be applied to '{}' and '{}'",
+                    self.r#type().as_str(),
+                    self.token.value(),
+                    vals[0].r#type,
+                    vals[1].r#type,
+                );
+                return Err(Error::TypeMismatch);
+            }
+
+            let r = (vals[0].value == vals[1].value) == Op::Eq.equal_type(self.token.r#type());
+            return Ok(Info::new(
+                None,
+                NodeType::BinOp,
+                Some(Value::new(NumberType::Boolean.r#type(), &r.to_string())),
+            ));
+        }
+
+        /* Strings and chars are concatenated by '+' and compared character by character */
+        let strings = vals
+            .iter()
+            .filter(|v| {
+                NumberType::Char.equal_type(&v.r#type) || NumberType::String.equal_type(&v.r#type)
+            })
+            .count();
+        if strings > 0 {
+            if strings != 2
+                || !(Op::Add.equal_type(self.token.r#type())
+                    || Op::is_relational(self.token.r#type()))
+            {
+                eprintln!(
+                    "[visit] [{}] op '{}' can not be applied to '{}' and '{}'",
+                    self.r#type().as_str(),
+                    self.token.value(),
+                    vals[0].r#type,
+                    vals[1].r#type,
+                );
+                return Err(Error::TypeMismatch);
+            }
+
+            let val = if Op::Add.equal_type(self.token.r#type()) {
+                Value::new(
+                    NumberType::String.r#type(),
+                    &(vals[0].value.clone() + &vals[1].value),
+                )
+            } else {
+                match compare::<String>(&vals[0].value, self.token.r#type(), &vals[1].value) {
+                    Ok(r) => Value::new(NumberType::Boolean.r#type(), &r.to_string()),
+                    Err(e) => return Err(e),
+                }
+            };
+            return Ok(Info::new(None, NodeType::BinOp, Some(val)));
+        }
+
+        /* Booleans can only be compared with booleans */
+        if (NumberType::Boolean.equal_type(&vals[0].r#type)
+            || NumberType::Boolean.equal_type(&vals[1].r#type))
+            && (vals[0].r#type != vals[1].r#type || !Op::is_relational(self.token.r#type()))
+        {
+            eprintln!(
+                "[visit] [{}] op '{}' can not be applied to '{}' and '{}'",
+                self.r#type().as_str(),
+                self.token.value(),
+                vals[0].r#type,
+                vals[1].r#type,
+            );
+            return Err(Error::TypeMismatch);
+        }
+
+        /* Enumerated values can only be compared with values of the same type, by position */
+        let enums = vals
+            .iter()
+            .filter(|v| matches!(DataType::from_tag(&v.r#type), Some(DataType::Enum(_))))
+            .count();
+        if enums > 0 {
+            if vals[0].r#type != vals[1].r#type || !Op::is_relational(self.token.r#type()) {
+                eprintln!(
+                    "[visit] [{}] op '{}' can not be applied to '{}' and '{}'",
+                    self.r#type().as_str(),
+                    self.token.value(),
+                    vals[0].r#type,
+                    vals[1].r#type,
+                );
+                return Err(Error::TypeMismatch);
+            }
+
+            let mut ordinals = Vec::new();
+            for v in vals.iter() {
+                match v.ordinal() {
+                    Ok(n) => ordinals.push(n.to_string()),
+                    Err(e) => return Err(e),
+                };
+            }
+            return match compare::<i32>(&ordinals[0], self.token.r#type(), &ordinals[1]) {
+                Ok(r) => Ok(Info::new(
+                    None,
+                    NodeType::BinOp,
+                    Some(Value::new(NumberType::Boolean.r#type(), &r.to_string())),
+                )),
+                Err(e) => Err(e),
+            };
+        }
+
         let val: Value;
-        if NumberType::Real.equal_type(&vals[0].r#type)
+        if Op::is_relational(self.token.r#type()) {
+            let r = if NumberType::Boolean.equal_type(&vals[0].r#type) {
+                compare::<bool>(&vals[0].value, self.token.r#type(), &vals[1].value)
+            } else if NumberType::Real.equal_type(&vals[0].r#type)
+                || NumberType::Real.equal_type(&vals[1].r#type)
+            {
+                compare::<f32>(&vals[0].value, self.token.r#type(), &vals[1].value)
+            } else {
+                compare::<i32>(&vals[0].value, self.token.r#type(), &vals[1].value)
+            };
+            let r = match r {
+                Ok(r) => r,
+                Err(e) => return Err(e),
+            };
+            val = Value::new(NumberType::Boolean.r#type(), &r.to_string())
+        } else if Op::IntDiv.equal_type(self.token.r#type())
+            || Op::Mod.equal_type(self.token.r#type())
+        {
+            /* DIV and MOD only apply to integers */
+            if !NumberType::Integer.equal_type(&vals[0].r#type)
+                || !NumberType::Integer.equal_type(&vals[1].r#type)
+            {
+                eprintln!(
+                    "[visit] [{}] op '{}' can not be applied to '{}' and '{}'",
+                    self.r#type().as_str(),
+                    self.token.value(),
+                    vals[0].r#type,
+                    vals[1].r#type,
+                );
+                return Err(Error::TypeMismatch);
+            }
+            let r = match int_div(&vals[0].value, self.token.r#type(), &vals[1].value) {
+                Ok(r) => r,
+                Err(e) => return Err(e),
+            };
+            val = Value::new(NumberType::Integer.r#type(), &r.to_string())
+        } else if NumberType::Real.equal_type(&vals[0].r#type)
             || NumberType::Real.equal_type(&vals[1].r#type)
+            || Op::Div.equal_type(self.token.r#type())
         {
+            /* '/' always results in a REAL */
             let r = match cal::<f32>(&vals[0].value, self.token.r#type(), &vals[1].value) {
                 Ok(r) => r,
                 Err(e) => return Err(e),
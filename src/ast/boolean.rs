@@ -0,0 +1,33 @@
+use super::{Info, Node, NodeType, Value};
+use crate::error::Error;
+use crate::global_scope::Scope;
+use crate::lexer::lexeme::number::NumberType;
+use crate::lexer::lexeme::Type;
+use std::{cell::RefCell, rc::Rc};
+
+pub struct Boolean {
+    value: bool,
+}
+
+impl Boolean {
+    pub fn new(value: bool) -> Boolean {
+        Boolean { value }
+    }
+}
+
+impl Node for Boolean {
+    fn r#type(&self) -> NodeType {
+        NodeType::Boolean
+    }
+
+    fn visit(&self, _scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        Ok(Info::new(
+            None,
+            NodeType::Boolean,
+            Some(Value::new(
+                NumberType::Boolean.r#type(),
+                &self.value.to_string(),
+            )),
+        ))
+    }
+}
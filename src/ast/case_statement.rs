@@ -0,0 +1,161 @@
+use super::{evaluate, Info, Node, NodeType, Value};
+use crate::error::Error;
+use crate::global_scope::Scope;
+use std::{cell::RefCell, rc::Rc};
+
+/// CaseLabel is a single constant or a constant range (low..high) of a case element.
+pub struct CaseLabel {
+    low: Rc<dyn Node>,
+    high: Option<Rc<dyn Node>>,
+}
+
+impl CaseLabel {
+    pub fn new(low: Rc<dyn Node>, high: Option<Rc<dyn Node>>) -> CaseLabel {
+        CaseLabel { low, high }
+    }
+}
+
+/// CaseElement is a labelled statement of the case statement.
+pub struct CaseElement {
+    labels: Vec<CaseLabel>,
+    statement: Rc<dyn Node>,
+}
+
+impl CaseElement {
+    pub fn new(labels: Vec<CaseLabel>, statement: Rc<dyn Node>) -> CaseElement {
+        CaseElement { labels, statement }
+    }
+}
+
+pub struct Case {
+    selector: Rc<dyn Node>,
+    elements: Vec<CaseElement>,
+    else_children: Option<Vec<Rc<dyn Node>>>,
+}
+
+impl Case {
+    pub fn new(
+        selector: Rc<dyn Node>,
+        elements: Vec<CaseElement>,
+        else_children: Option<Vec<Rc<dyn Node>>>,
+    ) -> Case {
+        Case {
+            selector,
+            elements,
+            else_children,
+        }
+    }
+
+    /// label_ordinal returns the ordinal number of a label, which must have the selector's type.
+    fn label_ordinal(
+        &self,
+        node: &Rc<dyn Node>,
+        selector: &Value,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<i32>, Error> {
+        let val = match evaluate(node, scope) {
+            Ok(Some(v)) => v,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        if val.r#type() != selector.r#type() {
+            eprintln!(
+                "[visit] [{}] label of type {} does not match selector of type {}",
+                self.r#type().as_str(),
+                val.r#type(),
+                selector.r#type()
+            );
+            return Err(Error::TypeMismatch);
+        }
+
+        match val.ordinal() {
+            Ok(n) => Ok(Some(n)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// matches reports whether the selector matches one of the labels of the element, nothing is
+    /// reported once the program is halted.
+    fn matches(
+        &self,
+        element: &CaseElement,
+        selector: &Value,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<bool>, Error> {
+        let ordinal = match selector.ordinal() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        for label in element.labels.iter() {
+            let low = match self.label_ordinal(&label.low, selector, scope.clone()) {
+                Ok(Some(n)) => n,
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+            let high = match &label.high {
+                Some(h) => match self.label_ordinal(h, selector, scope.clone()) {
+                    Ok(Some(n)) => n,
+                    Ok(None) => return Ok(None),
+                    Err(e) => return Err(e),
+                },
+                None => low,
+            };
+
+            if low <= ordinal && ordinal <= high {
+                return Ok(Some(true));
+            }
+        }
+
+        Ok(Some(false))
+    }
+}
+
+impl Node for Case {
+    fn r#type(&self) -> NodeType {
+        NodeType::Case
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let selector = match evaluate(&self.selector, scope.clone()) {
+            Ok(Some(v)) => v,
+            Ok(None) => return Ok(Info::new(None, NodeType::Case, None)),
+            Err(e) => return Err(e),
+        };
+
+        for element in self.elements.iter() {
+            match self.matches(element, &selector, scope.clone()) {
+                Ok(Some(true)) => {
+                    return match element.statement.visit(scope.clone()) {
+                        Ok(_) => Ok(Info::new(None, NodeType::Case, None)),
+                        Err(e) => Err(e),
+                    };
+                }
+                Ok(Some(false)) => continue,
+                Ok(None) => return Ok(Info::new(None, NodeType::Case, None)),
+                Err(e) => return Err(e),
+            };
+        }
+
+        let children = match &self.else_children {
+            Some(children) => children,
+            None => {
+                eprintln!(
+                    "[visit] [{}] no label matches the selector '{}' and there is no ELSE",
+                    self.r#type().as_str(),
+                    selector.value()
+                );
+                return Err(Error::CaseLabelNotFound);
+            }
+        };
+
+        for c in children.iter() {
+            if let Err(e) = c.visit(scope.clone()) {
+                return Err(e);
+            }
+        }
+
+        Ok(Info::new(None, NodeType::Case, None))
+    }
+}
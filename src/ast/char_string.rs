@@ -0,0 +1,35 @@
+use super::{Info, Node, NodeType, Value};
+use crate::error::Error;
+use crate::global_scope::Scope;
+use crate::lexer::lexeme::number::NumberType;
+use crate::lexer::lexeme::Type;
+use std::{cell::RefCell, rc::Rc};
+
+/// CharString is a character string literal, its type is CHAR if it has exactly one character.
+pub struct CharString {
+    r#type: NumberType,
+    value: String,
+}
+
+impl CharString {
+    pub fn new(r#type: NumberType, value: &str) -> CharString {
+        CharString {
+            r#type,
+            value: value.to_string(),
+        }
+    }
+}
+
+impl Node for CharString {
+    fn r#type(&self) -> NodeType {
+        NodeType::CharString
+    }
+
+    fn visit(&self, _scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        Ok(Info::new(
+            None,
+            NodeType::CharString,
+            Some(Value::new(self.r#type.r#type(), &self.value)),
+        ))
+    }
+}
@@ -1,16 +1,18 @@
 use super::Info;
 use super::{Node, NodeType};
 use crate::error::Error;
-use crate::global_scope::Scope;
+use crate::global_scope::{Scope, Signal};
+use std::collections::HashMap;
 use std::{cell::RefCell, rc::Rc};
 
 pub struct Compound {
     children: Vec<Rc<dyn Node>>,
+    labels: HashMap<u32, usize>, // The position of the statement with the label in the children.
 }
 
 impl Compound {
-    pub fn new(children: Vec<Rc<dyn Node>>) -> Compound {
-        Compound { children }
+    pub fn new(children: Vec<Rc<dyn Node>>, labels: HashMap<u32, usize>) -> Compound {
+        Compound { children, labels }
     }
 }
 
@@ -19,11 +21,27 @@ impl Node for Compound {
         NodeType::Compound
     }
 
+    /// visit executes the statements in order. A GOTO to a label of this compound statement
+    /// continues with the labelled statement, any other transfer of control leaves it.
     fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
-        for c in self.children.iter() {
-            if let Err(e) = c.visit(scope.clone()) {
+        let mut i = 0;
+        while i < self.children.len() {
+            if let Err(e) = self.children[i].visit(scope.clone()) {
                 return Err(e);
             }
+
+            let signal = scope.borrow().signal();
+            match signal {
+                Some(Signal::Goto(label)) => match self.labels.get(&label) {
+                    Some(index) => {
+                        scope.borrow_mut().clear_signal();
+                        i = *index;
+                    }
+                    None => break,
+                },
+                Some(_) => break,
+                None => i += 1,
+            }
         }
         Ok(Info::new(None, NodeType::Compound, None))
     }
@@ -0,0 +1,65 @@
+use super::{Info, Node, NodeType};
+use crate::data_type::DataType;
+use crate::error::Error;
+use crate::global_scope::{ConstantSymbol, Identifier, Scope};
+use std::{cell::RefCell, rc::Rc};
+
+pub struct ConstDecl {
+    name: String,
+    expr: Rc<dyn Node>,
+}
+
+impl ConstDecl {
+    pub fn new(name: &str, expr: Rc<dyn Node>) -> ConstDecl {
+        ConstDecl {
+            name: name.to_string(),
+            expr,
+        }
+    }
+}
+
+impl Node for ConstDecl {
+    fn r#type(&self) -> NodeType {
+        NodeType::ConstDecl
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        /* The constant expression is evaluated once, at declaration time */
+        let val = match self.expr.visit(scope.clone()) {
+            Ok(info) => match info.value() {
+                Some(v) => v,
+                None => {
+                    eprintln!(
+                        "[visit] [{}] value of constant '{}' not found",
+                        self.r#type().as_str(),
+                        self.name
+                    );
+                    return Err(Error::InvalidSyntax);
+                }
+            },
+            Err(e) => return Err(e),
+        };
+
+        /* The type of the constant is the type of its value, e.g. an enumerated type */
+        let r#type = match DataType::from_tag(val.r#type()) {
+            Some(t) => t,
+            None => {
+                eprintln!(
+                    "[visit] [{}] type of constant '{}' not found",
+                    self.r#type().as_str(),
+                    self.name
+                );
+                return Err(Error::TypeNotFound);
+            }
+        };
+
+        if let Err(e) = scope.borrow_mut().define(
+            &self.name,
+            Identifier::Constant(ConstantSymbol::new(r#type, val.clone())),
+        ) {
+            return Err(e);
+        }
+
+        Ok(Info::new(Some(self.name.clone()), self.r#type(), Some(val)))
+    }
+}
@@ -1,9 +1,13 @@
-use super::{procedure::Procedure, var_decl::VarDecl, Info, Node, NodeType};
+use super::{
+    const_decl::ConstDecl, procedure::Procedure, type_decl::TypeDecl, var_decl::VarDecl, Info,
+    Node, NodeType,
+};
 use crate::error::Error;
 use crate::global_scope::Scope;
 use std::{cell::RefCell, rc::Rc};
 
 pub struct Declaration {
+    definition_list: Vec<Rc<dyn Node>>, // The constant and type definitions in the order of the code.
     var_decl_list: Vec<Rc<VarDecl>>,
     procedure_list: Vec<Rc<Procedure>>,
 }
@@ -11,11 +15,20 @@ pub struct Declaration {
 impl Declaration {
     pub fn new(var_decl_list: Vec<Rc<VarDecl>>, procedure_list: Vec<Rc<Procedure>>) -> Declaration {
         Declaration {
+            definition_list: Vec::new(),
             var_decl_list,
             procedure_list,
         }
     }
 
+    pub fn const_decl_list_push(&mut self, cd: Rc<ConstDecl>) {
+        self.definition_list.push(cd.clone());
+    }
+
+    pub fn type_decl_list_push(&mut self, td: Rc<TypeDecl>) {
+        self.definition_list.push(td.clone());
+    }
+
     pub fn var_decl_list_push(&mut self, vd: Rc<VarDecl>) {
         self.var_decl_list.push(vd.clone());
     }
@@ -31,6 +44,13 @@ impl Node for Declaration {
     }
 
     fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        /* A constant may have a value of a type defined before it, and a type may use a constant */
+        for d in self.definition_list.iter() {
+            if let Err(e) = d.visit(scope.clone()) {
+                return Err(e);
+            }
+        }
+
         for vd in self.var_decl_list.iter() {
             if let Err(e) = vd.visit(scope.clone()) {
                 return Err(e);
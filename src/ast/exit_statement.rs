@@ -0,0 +1,58 @@
+use super::procedure::RETURN_VALUE;
+use super::{evaluate, Info, Node, NodeType};
+use crate::error::Error;
+use crate::global_scope::{Identifier, Scope, Signal, VariableSymbol};
+use std::{cell::RefCell, rc::Rc};
+
+/// Exit leaves the procedure or function, or the program. `Exit(value)` sets the result of the
+/// function before leaving it.
+pub struct Exit {
+    value: Option<Rc<dyn Node>>,
+}
+
+impl Exit {
+    pub fn new(value: Option<Rc<dyn Node>>) -> Exit {
+        Exit { value }
+    }
+}
+
+impl Node for Exit {
+    fn r#type(&self) -> NodeType {
+        NodeType::Exit
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        if let Some(node) = &self.value {
+            let val = match evaluate(node, scope.clone()) {
+                Ok(Some(v)) => v,
+                Ok(None) => return Ok(Info::new(None, NodeType::Exit, None)),
+                Err(e) => return Err(e),
+            };
+
+            let r#type = match scope.borrow().get(RETURN_VALUE) {
+                Some(Identifier::Variable(vs)) => vs.r#type(),
+                _ => {
+                    eprintln!(
+                        "[visit] [{}] a value can only be returned from a function",
+                        self.r#type().as_str()
+                    );
+                    return Err(Error::InvalidSyntax);
+                }
+            };
+            let val = match val.cast(&r#type) {
+                Ok(v) => v,
+                Err(e) => return Err(e),
+            };
+
+            if let Err(e) = scope.borrow_mut().set(
+                RETURN_VALUE,
+                Identifier::Variable(VariableSymbol::new(r#type, Some(val))),
+            ) {
+                return Err(e);
+            }
+        }
+
+        scope.borrow_mut().raise(Signal::Exit);
+        Ok(Info::new(None, NodeType::Exit, None))
+    }
+}
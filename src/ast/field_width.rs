@@ -0,0 +1,83 @@
+use super::{evaluate, Info, Node, NodeType, Value};
+use crate::console;
+use crate::error::Error;
+use crate::global_scope::Scope;
+use crate::lexer::lexeme::number::NumberType;
+use crate::lexer::lexeme::Type;
+use std::{cell::RefCell, rc::Rc};
+
+/// FieldWidth is an argument of Write with a field width, `x:width` or `x:width:decimals`, its
+/// value is the text of `x` as Write writes it.
+pub struct FieldWidth {
+    value: Rc<dyn Node>,
+    width: Rc<dyn Node>,
+    decimals: Option<Rc<dyn Node>>,
+}
+
+impl FieldWidth {
+    pub fn new(value: Rc<dyn Node>, width: Rc<dyn Node>, decimals: Option<Rc<dyn Node>>) -> Self {
+        FieldWidth {
+            value,
+            width,
+            decimals,
+        }
+    }
+
+    /// number returns the value of the width or the number of decimals, which is an INTEGER.
+    fn number(&self, node: &Rc<dyn Node>, scope: Rc<RefCell<Scope>>) -> Result<Option<i32>, Error> {
+        let val = match evaluate(node, scope) {
+            Ok(Some(v)) => v,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if val.r#type() != NumberType::Integer.r#type() {
+            eprintln!(
+                "[visit] [{}] field width of type {} must be an INTEGER",
+                self.r#type().as_str(),
+                val.r#type()
+            );
+            return Err(Error::TypeMismatch);
+        }
+        match val.value().parse::<i32>() {
+            Ok(n) => Ok(Some(n)),
+            Err(_) => Err(Error::InvalidSyntax),
+        }
+    }
+}
+
+impl Node for FieldWidth {
+    fn r#type(&self) -> NodeType {
+        NodeType::FieldWidth
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let halted = Ok(Info::new(None, NodeType::FieldWidth, None));
+        let val = match evaluate(&self.value, scope.clone()) {
+            Ok(Some(v)) => v,
+            Ok(None) => return halted,
+            Err(e) => return Err(e),
+        };
+        let width = match self.number(&self.width, scope.clone()) {
+            Ok(Some(n)) => n,
+            Ok(None) => return halted,
+            Err(e) => return Err(e),
+        };
+        let decimals = match &self.decimals {
+            Some(node) => match self.number(node, scope) {
+                Ok(Some(n)) => Some(n),
+                Ok(None) => return halted,
+                Err(e) => return Err(e),
+            },
+            None => None,
+        };
+
+        match console::format(&val, Some(width), decimals) {
+            Ok(text) => Ok(Info::new(
+                None,
+                NodeType::FieldWidth,
+                Some(Value::new(NumberType::String.r#type(), &text)),
+            )),
+            Err(e) => Err(e),
+        }
+    }
+}
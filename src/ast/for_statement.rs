@@ -0,0 +1,143 @@
+use super::{evaluate, Info, Node, NodeType};
+use crate::data_type::DataType;
+use crate::error::Error;
+use crate::global_scope::{Identifier, Scope, VariableSymbol};
+use std::{cell::RefCell, rc::Rc};
+
+pub struct For {
+    variable: String,
+    start: Rc<dyn Node>,
+    end: Rc<dyn Node>,
+    downto: bool,
+    body: Rc<dyn Node>,
+}
+
+impl For {
+    pub fn new(
+        variable: &str,
+        start: Rc<dyn Node>,
+        end: Rc<dyn Node>,
+        downto: bool,
+        body: Rc<dyn Node>,
+    ) -> For {
+        For {
+            variable: variable.to_string(),
+            start,
+            end,
+            downto,
+            body,
+        }
+    }
+
+    /// bound evaluates a bound of the loop and returns its ordinal number, the bound has no value
+    /// once the program is halted.
+    fn bound(
+        &self,
+        node: &Rc<dyn Node>,
+        r#type: &DataType,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<i32>, Error> {
+        let val = match evaluate(node, scope) {
+            Ok(Some(v)) => v,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        if r#type.tag() != val.r#type() {
+            eprintln!(
+                "[visit] [{}] bound type {} does not match control variable '{}' of type {}",
+                self.r#type().as_str(),
+                val.r#type(),
+                self.variable,
+                r#type
+            );
+            return Err(Error::TypeMismatch);
+        }
+
+        match val.ordinal() {
+            Ok(n) => Ok(Some(n)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Node for For {
+    fn r#type(&self) -> NodeType {
+        NodeType::For
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let vs = match scope.borrow().get(&self.variable) {
+            Some(Identifier::Variable(vs)) => vs,
+            Some(_) => {
+                eprintln!(
+                    "[visit] [{}] control variable '{}' is not a variable",
+                    self.r#type().as_str(),
+                    self.variable
+                );
+                return Err(Error::InvalidSyntax);
+            }
+            None => {
+                eprintln!(
+                    "[visit] [{}] control variable '{}' is not defined",
+                    self.r#type().as_str(),
+                    self.variable
+                );
+                return Err(Error::VarNotFound);
+            }
+        };
+
+        let r#type = match vs.r#type() {
+            t if t.is_ordinal() => t,
+            _ => {
+                eprintln!(
+                    "[visit] [{}] control variable '{}' must be of an ordinal type",
+                    self.r#type().as_str(),
+                    self.variable
+                );
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        /* The bounds are evaluated only once, before the first iteration */
+        let start = match self.bound(&self.start, &r#type, scope.clone()) {
+            Ok(Some(n)) => n,
+            Ok(None) => return Ok(Info::new(None, NodeType::For, None)),
+            Err(e) => return Err(e),
+        };
+        let end = match self.bound(&self.end, &r#type, scope.clone()) {
+            Ok(Some(n)) => n,
+            Ok(None) => return Ok(Info::new(None, NodeType::For, None)),
+            Err(e) => return Err(e),
+        };
+
+        let step: i64 = if self.downto { -1 } else { 1 };
+        let mut i = start as i64;
+        while (!self.downto && i <= end as i64) || (self.downto && i >= end as i64) {
+            /* A bound out of the range of a subrange control variable fails on assignment */
+            let val = match r#type.value_at(i as i32) {
+                Ok(v) => v,
+                Err(e) => return Err(e),
+            };
+            if let Err(e) = scope.borrow_mut().set(
+                &self.variable,
+                Identifier::Variable(VariableSymbol::new(r#type.clone(), Some(val))),
+            ) {
+                return Err(e);
+            }
+
+            if let Err(e) = self.body.visit(scope.clone()) {
+                return Err(e);
+            }
+
+            /* BREAK ends the loop, and so does a transfer of control out of it */
+            if scope.borrow_mut().end_iteration() {
+                break;
+            }
+
+            i += step;
+        }
+
+        Ok(Info::new(None, NodeType::For, None))
+    }
+}
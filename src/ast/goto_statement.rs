@@ -0,0 +1,28 @@
+use super::{Info, Node, NodeType};
+use crate::error::Error;
+use crate::global_scope::{Scope, Signal};
+use std::{cell::RefCell, rc::Rc};
+
+/// Goto transfers control to the statement with the label. The statements being executed stop
+/// until the compound statement holding the labelled statement is reached, the parser makes sure
+/// it encloses the GOTO.
+pub struct Goto {
+    label: u32,
+}
+
+impl Goto {
+    pub fn new(label: u32) -> Goto {
+        Goto { label }
+    }
+}
+
+impl Node for Goto {
+    fn r#type(&self) -> NodeType {
+        NodeType::Goto
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        scope.borrow_mut().raise(Signal::Goto(self.label));
+        Ok(Info::new(None, NodeType::Goto, None))
+    }
+}
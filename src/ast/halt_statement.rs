@@ -0,0 +1,49 @@
+use super::{evaluate, Info, Node, NodeType};
+use crate::error::Error;
+use crate::global_scope::{Scope, Signal};
+use crate::lexer::lexeme::number::NumberType;
+use crate::lexer::lexeme::Type;
+use std::{cell::RefCell, rc::Rc};
+
+/// Halt stops the program, the exit code is 0 unless it is given.
+pub struct Halt {
+    code: Option<Rc<dyn Node>>,
+}
+
+impl Halt {
+    pub fn new(code: Option<Rc<dyn Node>>) -> Halt {
+        Halt { code }
+    }
+}
+
+impl Node for Halt {
+    fn r#type(&self) -> NodeType {
+        NodeType::Halt
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let code = match &self.code {
+            Some(node) => match evaluate(node, scope.clone()) {
+                Ok(Some(v)) if v.r#type() == NumberType::Integer.r#type() => match v.ordinal() {
+                    Ok(n) => n,
+                    Err(e) => return Err(e),
+                },
+                Ok(Some(v)) => {
+                    eprintln!(
+                        "[visit] [{}] exit code of type {} must be an INTEGER",
+                        self.r#type().as_str(),
+                        v.r#type()
+                    );
+                    return Err(Error::TypeMismatch);
+                }
+                /* The program is already halted by a function computing the exit code */
+                Ok(None) => return Ok(Info::new(None, NodeType::Halt, None)),
+                Err(e) => return Err(e),
+            },
+            None => 0,
+        };
+
+        scope.borrow_mut().raise(Signal::Halt(code));
+        Ok(Info::new(None, NodeType::Halt, None))
+    }
+}
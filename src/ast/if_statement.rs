@@ -0,0 +1,83 @@
+use super::{evaluate, Info, Node, NodeType};
+use crate::error::Error;
+use crate::global_scope::Scope;
+use crate::lexer::lexeme::number::NumberType;
+use crate::lexer::lexeme::Type;
+use std::{cell::RefCell, rc::Rc};
+
+pub struct If {
+    condition: Rc<dyn Node>,
+    then_branch: Rc<dyn Node>,
+    else_branch: Option<Rc<dyn Node>>,
+}
+
+impl If {
+    pub fn new(
+        condition: Rc<dyn Node>,
+        then_branch: Rc<dyn Node>,
+        else_branch: Option<Rc<dyn Node>>,
+    ) -> If {
+        If {
+            condition,
+            then_branch,
+            else_branch,
+        }
+    }
+}
+
+/// check_condition visits the condition node and returns its boolean value, a condition has no
+/// value once the program is halted.
+pub fn check_condition(
+    condition: &Rc<dyn Node>,
+    scope: Rc<RefCell<Scope>>,
+) -> Result<Option<bool>, Error> {
+    let val = match evaluate(condition, scope) {
+        Ok(Some(v)) => v,
+        Ok(None) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    if !NumberType::Boolean.equal_type(val.r#type()) {
+        eprintln!(
+            "[visit] [condition] condition must be {}, got {}",
+            NumberType::Boolean.r#type(),
+            val.r#type()
+        );
+        return Err(Error::TypeMismatch);
+    }
+
+    match val.value().parse::<bool>() {
+        Ok(b) => Ok(Some(b)),
+        Err(e) => {
+            eprintln!(
+                "[visit] [condition] parse boolean '{}' failed, error: {}",
+                val.value(),
+                e
+            );
+            Err(Error::InvalidSyntax)
+        }
+    }
+}
+
+impl Node for If {
+    fn r#type(&self) -> NodeType {
+        NodeType::If
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let branch = match check_condition(&self.condition, scope.clone()) {
+            Ok(Some(true)) => Some(self.then_branch.clone()),
+            Ok(Some(false)) => self.else_branch.clone(),
+            Ok(None) => None,
+            Err(e) => return Err(e),
+        };
+
+        if let Some(b) = branch {
+            if let Err(e) = b.visit(scope.clone()) {
+                return Err(e);
+            }
+        }
+
+        Ok(Info::new(None, NodeType::If, None))
+    }
+}
@@ -21,7 +21,7 @@ impl Node for Integer {
         NodeType::Integer
     }
 
-    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+    fn visit(&self, _scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
         Ok(Info::new(
             None,
             NodeType::Integer,
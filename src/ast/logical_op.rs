@@ -0,0 +1,73 @@
+use super::if_statement::check_condition;
+use super::{Info, Node, NodeType, Value};
+use crate::error::Error;
+use crate::global_scope::Scope;
+use crate::lexer::lexeme::number::NumberType;
+use crate::lexer::lexeme::{op::Op, Type};
+use crate::token::Token;
+use std::{cell::RefCell, rc::Rc};
+
+/// LogicalOp is the AND or OR of two boolean operands.
+pub struct LogicalOp {
+    left: Rc<dyn Node>,
+    token: Token,
+    right: Rc<dyn Node>,
+    complete: bool, // Evaluate the right operand even if the left one decides the result.
+}
+
+impl LogicalOp {
+    pub fn new(left: Rc<dyn Node>, token: Token, right: Rc<dyn Node>, complete: bool) -> LogicalOp {
+        LogicalOp {
+            left,
+            token,
+            right,
+            complete,
+        }
+    }
+}
+
+impl Node for LogicalOp {
+    fn r#type(&self) -> NodeType {
+        NodeType::LogicalOp
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let is_and = Op::And.equal_type(self.token.r#type());
+        if !is_and && !Op::Or.equal_type(self.token.r#type()) {
+            eprintln!(
+                "[visit] [{}] invalid op {}",
+                self.r#type().as_str(),
+                self.token.r#type()
+            );
+            return Err(Error::InvalidSyntax);
+        }
+
+        let left = match check_condition(&self.left, scope.clone()) {
+            Ok(Some(b)) => b,
+            Ok(None) => return Ok(Info::new(None, NodeType::LogicalOp, None)),
+            Err(e) => return Err(e),
+        };
+
+        /* Short-circuit: FALSE AND x is FALSE, TRUE OR x is TRUE */
+        let r = if !self.complete && left != is_and {
+            left
+        } else {
+            let right = match check_condition(&self.right, scope.clone()) {
+                Ok(Some(b)) => b,
+                Ok(None) => return Ok(Info::new(None, NodeType::LogicalOp, None)),
+                Err(e) => return Err(e),
+            };
+            if is_and {
+                left && right
+            } else {
+                left || right
+            }
+        };
+
+        Ok(Info::new(
+            None,
+            NodeType::LogicalOp,
+            Some(Value::new(NumberType::Boolean.r#type(), &r.to_string())),
+        ))
+    }
+}
@@ -0,0 +1,27 @@
+use super::{Info, Node, NodeType};
+use crate::error::Error;
+use crate::global_scope::{Scope, Signal};
+use std::{cell::RefCell, rc::Rc};
+
+/// LoopControl is BREAK, which leaves the innermost loop, or CONTINUE, which starts its next
+/// iteration. The parser makes sure it is inside a loop.
+pub struct LoopControl {
+    signal: Signal,
+}
+
+impl LoopControl {
+    pub fn new(signal: Signal) -> LoopControl {
+        LoopControl { signal }
+    }
+}
+
+impl Node for LoopControl {
+    fn r#type(&self) -> NodeType {
+        NodeType::LoopControl
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        scope.borrow_mut().raise(self.signal);
+        Ok(Info::new(None, NodeType::LoopControl, None))
+    }
+}
@@ -1,21 +1,45 @@
+use crate::data_type::{DataType, Selection, EMPTY_SET, POINTER};
+use crate::lexer::lexeme::{number::NumberType, Type};
 use crate::{error::Error, global_scope::Scope};
+use itertools::Itertools;
+use std::collections::BTreeSet;
 use std::fmt::{self, Display};
 use std::{cell::RefCell, rc::Rc};
 
+pub mod address_of;
 pub mod assign;
 pub mod bin_op;
 pub mod block;
+pub mod boolean;
+pub mod case_statement;
+pub mod char_string;
 pub mod compound;
+pub mod const_decl;
 pub mod declaration;
+pub mod exit_statement;
+pub mod field_width;
+pub mod for_statement;
+pub mod goto_statement;
+pub mod halt_statement;
+pub mod if_statement;
 pub mod integer;
+pub mod logical_op;
+pub mod loop_control;
+pub mod nil;
 pub mod no_op;
 pub mod procedure;
 pub mod procedure_call;
 pub mod program;
 pub mod real;
+pub mod repeat_statement;
+pub mod set_constructor;
+pub mod type_decl;
+pub mod type_spec;
 pub mod unary_op;
 pub mod var;
 pub mod var_decl;
+pub mod while_statement;
+pub mod with_statement;
 
 pub enum NodeType {
     Unknown,
@@ -33,6 +57,25 @@ pub enum NodeType {
     Declaration,
     ProcedureCall,
     Procedure,
+    If,
+    While,
+    Repeat,
+    For,
+    Case,
+    Boolean,
+    LogicalOp,
+    ConstDecl,
+    TypeDecl,
+    CharString,
+    SetConstructor,
+    Nil,
+    AddressOf,
+    FieldWidth,
+    Goto,
+    With,
+    LoopControl,
+    Exit,
+    Halt,
 }
 
 impl NodeType {
@@ -53,6 +96,25 @@ impl NodeType {
             NodeType::Declaration => "Declaration",
             NodeType::ProcedureCall => "Procedure Call",
             NodeType::Procedure => "Procedure",
+            NodeType::If => "If",
+            NodeType::While => "While",
+            NodeType::Repeat => "Repeat",
+            NodeType::For => "For",
+            NodeType::Case => "Case",
+            NodeType::Boolean => "Boolean",
+            NodeType::LogicalOp => "LogicalOp",
+            NodeType::ConstDecl => "Constant Declaration",
+            NodeType::TypeDecl => "Type Declaration",
+            NodeType::CharString => "Character String",
+            NodeType::SetConstructor => "Set Constructor",
+            NodeType::Nil => "Nil",
+            NodeType::AddressOf => "Address Of",
+            NodeType::FieldWidth => "Field Width",
+            NodeType::Goto => "Goto",
+            NodeType::With => "With",
+            NodeType::LoopControl => "Loop Control",
+            NodeType::Exit => "Exit",
+            NodeType::Halt => "Halt",
         }
     }
 }
@@ -68,6 +130,7 @@ pub struct Info {
 pub struct Value {
     r#type: String,
     value: String,
+    items: Vec<Value>, // The elements of a value of a structured type.
 }
 
 impl Value {
@@ -75,6 +138,25 @@ impl Value {
         Value {
             r#type: r#type.to_string(),
             value: value.to_string(),
+            items: Vec::new(),
+        }
+    }
+
+    /// structured builds the value of a structured type from the values of its elements.
+    pub fn structured(r#type: &str, items: Vec<Value>) -> Value {
+        Value {
+            r#type: r#type.to_string(),
+            value: String::new(),
+            items,
+        }
+    }
+
+    /// set builds a set value from its elements, a set is shown as [] when it is empty.
+    pub fn set(r#type: &str, items: Vec<Value>) -> Value {
+        Value {
+            r#type: r#type.to_string(),
+            value: String::from("[]"),
+            items,
         }
     }
 
@@ -82,6 +164,7 @@ impl Value {
         Value {
             r#type: self.r#type.clone(),
             value: self.value.clone(),
+            items: self.items.iter().map(|v| v.clone()).collect(),
         }
     }
 
@@ -92,11 +175,183 @@ impl Value {
     pub fn r#type(&self) -> &str {
         &self.r#type
     }
+
+    pub fn items(&self) -> &[Value] {
+        &self.items
+    }
+
+    /// content returns the text of the value, the elements of a structured value are listed in
+    /// brackets.
+    pub fn content(&self) -> String {
+        if self.items.is_empty() {
+            return self.value.clone();
+        }
+        format!("[{}]", self.items.iter().map(|v| v.content()).join(", "))
+    }
+
+    /// cast converts the value to the type for assignment, an INTEGER can be converted to a REAL
+    /// and a CHAR to a STRING, a structured value can only be assigned to a variable of the same
+    /// type. A value assigned to a subrange must be in its range, an untyped pointer can be
+    /// assigned to a pointer of any type.
+    pub fn cast(&self, r#type: &DataType) -> Result<Value, Error> {
+        let r#type = match r#type {
+            DataType::Simple(t) => *t,
+            DataType::Subrange(_) => {
+                let ordinal = match DataType::from_tag(&self.r#type) {
+                    Some(t) if t.tag() == r#type.tag() => match self.ordinal() {
+                        Ok(n) => n,
+                        Err(e) => return Err(e),
+                    },
+                    _ => {
+                        eprintln!(
+                            "[value] [cast] {} can not be converted to {}",
+                            self.r#type, r#type
+                        );
+                        return Err(Error::TypeMismatch);
+                    }
+                };
+                return r#type.value_at(ordinal);
+            }
+            DataType::Pointer(_) => {
+                if self.r#type != POINTER && self.r#type != r#type.tag() {
+                    eprintln!(
+                        "[value] [cast] {} can not be converted to {}",
+                        self.r#type, r#type
+                    );
+                    return Err(Error::TypeMismatch);
+                }
+                return Ok(Value::new(&r#type.tag(), &self.value));
+            }
+            /* The elements of a set must be in the range of its base type */
+            DataType::Set(st) => {
+                if self.r#type != EMPTY_SET && self.r#type != r#type.tag() {
+                    eprintln!(
+                        "[value] [cast] {} can not be converted to {}",
+                        self.r#type, r#type
+                    );
+                    return Err(Error::TypeMismatch);
+                }
+                return match self.ordinals() {
+                    Ok(ordinals) => st.value(&ordinals),
+                    Err(e) => Err(e),
+                };
+            }
+            /* A string longer than the maximum length of the STRING[n] is truncated */
+            DataType::ShortString(n) => {
+                return match self.cast(&DataType::Simple(NumberType::String)) {
+                    Ok(v) => Ok(Value::new(
+                        v.r#type(),
+                        &v.value.chars().take(*n).collect::<String>(),
+                    )),
+                    Err(e) => Err(e),
+                };
+            }
+            _ => {
+                if self.r#type == r#type.tag() {
+                    return Ok(self.clone());
+                }
+                eprintln!(
+                    "[value] [cast] {} can not be converted to {}",
+                    self.r#type, r#type
+                );
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        if r#type.equal_type(&self.r#type) {
+            return Ok(self.clone());
+        }
+
+        if r#type == NumberType::String && NumberType::Char.equal_type(&self.r#type) {
+            return Ok(Value::new(r#type.r#type(), &self.value));
+        }
+
+        if r#type == NumberType::Real && NumberType::Integer.equal_type(&self.r#type) {
+            return match self.value.parse::<f32>() {
+                Ok(n) => Ok(Value::new(r#type.r#type(), &n.to_string())),
+                Err(e) => {
+                    eprintln!(
+                        "[value] [cast] parse num '{}' failed, error: {}",
+                        self.value, e
+                    );
+                    Err(Error::InvalidSyntax)
+                }
+            };
+        }
+
+        eprintln!(
+            "[value] [cast] {} can not be converted to {}",
+            self.r#type,
+            r#type.r#type()
+        );
+        Err(Error::TypeMismatch)
+    }
+
+    /// ordinal returns the ordinal number of a value of an ordinal type.
+    pub fn ordinal(&self) -> Result<i32, Error> {
+        if NumberType::Integer.equal_type(&self.r#type) {
+            if let Ok(n) = self.value.parse::<i32>() {
+                return Ok(n);
+            }
+        } else if NumberType::Boolean.equal_type(&self.r#type) {
+            if let Ok(b) = self.value.parse::<bool>() {
+                return Ok(b as i32);
+            }
+        } else if NumberType::Char.equal_type(&self.r#type) {
+            if let Some(c) = self.value.chars().next() {
+                return Ok(c as i32);
+            }
+        } else if let Some(DataType::Enum(et)) = DataType::from_tag(&self.r#type) {
+            if let Some(n) = et.position(&self.value) {
+                return Ok(n);
+            }
+        } else {
+            eprintln!("[value] [ordinal] '{}' is not an ordinal type", self.r#type);
+            return Err(Error::TypeMismatch);
+        }
+
+        eprintln!("[value] [ordinal] parse value '{}' failed", self.value);
+        Err(Error::InvalidSyntax)
+    }
+
+    /// ordinals returns the ordinal numbers of the elements of a set value.
+    pub fn ordinals(&self) -> Result<BTreeSet<i32>, Error> {
+        let mut ordinals = BTreeSet::new();
+        for v in self.items.iter() {
+            match v.ordinal() {
+                Ok(n) => ordinals.insert(n),
+                Err(e) => return Err(e),
+            };
+        }
+        Ok(ordinals)
+    }
+
+    /// from_ordinal builds the value of an ordinal type from its ordinal number.
+    pub fn from_ordinal(r#type: NumberType, ordinal: i32) -> Result<Value, Error> {
+        match r#type {
+            NumberType::Integer => Ok(Value::new(r#type.r#type(), &ordinal.to_string())),
+            NumberType::Boolean => Ok(Value::new(r#type.r#type(), &(ordinal != 0).to_string())),
+            NumberType::Char => match char::from_u32(ordinal as u32) {
+                Some(c) => Ok(Value::new(r#type.r#type(), &c.to_string())),
+                None => {
+                    eprintln!("[value] [from_ordinal] {} is not a character code", ordinal);
+                    Err(Error::InvalidSyntax)
+                }
+            },
+            _ => {
+                eprintln!(
+                    "[value] [from_ordinal] '{}' is not an ordinal type",
+                    r#type.r#type()
+                );
+                Err(Error::TypeMismatch)
+            }
+        }
+    }
 }
 
 impl Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "type: {}, value: {}", self.r#type, self.value)
+        write!(f, "type: {}, value: {}", self.r#type, self.content())
     }
 }
 
@@ -147,6 +402,25 @@ impl Display for Info {
     }
 }
 
+/// evaluate visits the node and returns its value. An expression has no value once the program is
+/// halted while it is evaluated, e.g. by a function it calls, which is not an error.
+pub fn evaluate(node: &Rc<dyn Node>, scope: Rc<RefCell<Scope>>) -> Result<Option<Value>, Error> {
+    match node.visit(scope.clone()) {
+        Ok(info) => match info.value() {
+            Some(v) => Ok(Some(v)),
+            None if scope.borrow().halted() => Ok(None),
+            None => {
+                eprintln!(
+                    "[visit] [{}] value not found in this node",
+                    node.r#type().as_str()
+                );
+                Err(Error::InvalidSyntax)
+            }
+        },
+        Err(e) => Err(e),
+    }
+}
+
 pub trait Node {
     fn r#type(&self) -> NodeType {
         NodeType::Unknown
@@ -156,6 +430,11 @@ pub trait Node {
         Err(Error::InvalidSyntax)
     }
 
+    /// selections evaluates the selectors following the name of a designator.
+    fn selections(&self, _scope: Rc<RefCell<Scope>>) -> Result<Vec<Selection>, Error> {
+        Err(Error::InvalidSyntax)
+    }
+
     fn visit(&self, _scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
         Err(Error::InvalidSyntax)
     }
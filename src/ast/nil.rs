@@ -0,0 +1,28 @@
+use super::{Info, Node, NodeType, Value};
+use crate::data_type::{NIL, POINTER};
+use crate::error::Error;
+use crate::global_scope::Scope;
+use std::{cell::RefCell, rc::Rc};
+
+/// Nil is the pointer which points to no variable, it can be assigned to a pointer of any type.
+pub struct Nil {}
+
+impl Nil {
+    pub fn new() -> Nil {
+        Nil {}
+    }
+}
+
+impl Node for Nil {
+    fn r#type(&self) -> NodeType {
+        NodeType::Nil
+    }
+
+    fn visit(&self, _scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        Ok(Info::new(
+            None,
+            NodeType::Nil,
+            Some(Value::new(POINTER, NIL)),
+        ))
+    }
+}
@@ -16,7 +16,7 @@ impl Node for NoOp {
         NodeType::NoOp
     }
 
-    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+    fn visit(&self, _scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
         Ok(Info::new(None, NodeType::NoOp, None))
     }
 }
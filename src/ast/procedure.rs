@@ -1,3 +1,4 @@
+use super::type_spec::TypeSpec;
 use super::{block::Block, var_decl::VarDecl};
 use super::{Info, Node, NodeType};
 use crate::global_scope::ProcedureSymbol;
@@ -7,9 +8,18 @@ use crate::{
 };
 use std::{cell::RefCell, rc::Rc};
 
+/// RESULT is the name through which a function reads and assigns its return value besides its
+/// own name, a parameter or a local declaration with that name hides it.
+pub const RESULT: &str = "Result";
+
+/// RETURN_VALUE is the key of the return value in the scope of a function call, it is not a valid
+/// identifier so no declaration can clash with it.
+pub const RETURN_VALUE: &str = "$Result";
+
 pub struct Procedure {
     name: String,
     var_decl_list: Vec<Rc<VarDecl>>,
+    return_type: Option<Rc<TypeSpec>>, // Only functions have a return type.
     block: Rc<Block>,
 }
 
@@ -18,16 +28,23 @@ impl Clone for Procedure {
         Self {
             name: self.name.clone(),
             var_decl_list: self.var_decl_list.clone(),
+            return_type: self.return_type.clone(),
             block: self.block.clone(),
         }
     }
 }
 
 impl Procedure {
-    pub fn new(name: &str, var_decl_list: Vec<Rc<VarDecl>>, block: Rc<Block>) -> Self {
+    pub fn new(
+        name: &str,
+        var_decl_list: Vec<Rc<VarDecl>>,
+        return_type: Option<Rc<TypeSpec>>,
+        block: Rc<Block>,
+    ) -> Self {
         Procedure {
             name: name.to_string(),
             var_decl_list,
+            return_type,
             block,
         }
     }
@@ -36,6 +53,10 @@ impl Procedure {
         self.var_decl_list.clone()
     }
 
+    pub fn return_type(&self) -> Option<Rc<TypeSpec>> {
+        self.return_type.clone()
+    }
+
     pub fn block(&self) -> Rc<Block> {
         self.block.clone()
     }
@@ -47,10 +68,11 @@ impl Node for Procedure {
     }
 
     fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
-        match scope.borrow_mut().define(
-            &self.name,
-            Identifier::Procedure(ProcedureSymbol::new(&self.name, Rc::new(self.clone()))),
-        ) {
+        let ps = ProcedureSymbol::new(&self.name, Rc::new(self.clone()), &scope);
+        match scope
+            .borrow_mut()
+            .define(&self.name, Identifier::Procedure(ps))
+        {
             Ok(_) => Ok(Info::new(None, NodeType::Procedure, None)),
             Err(e) => Err(e),
         }
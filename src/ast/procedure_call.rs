@@ -1,7 +1,9 @@
-use super::{Info, NodeType};
+use super::procedure::RETURN_VALUE;
+use super::var_decl::PassMode;
+use super::{evaluate, Info, NodeType};
+use crate::data_type::DataType;
 use crate::error::Error;
-use crate::global_scope::{Scope, VariableSymbol};
-use crate::lexer::lexeme::number::NumberType;
+use crate::global_scope::{ConstantSymbol, Heap, ReferenceSymbol, Scope, Signal, VariableSymbol};
 use crate::{ast::Node, global_scope::Identifier};
 use std::{cell::RefCell, rc::Rc};
 
@@ -19,19 +21,103 @@ impl ProcedureCall {
     }
 }
 
+impl ProcedureCall {
+    /// reference returns the reference to the variable passed to a VAR or OUT parameter and the
+    /// type of the variable, the indexes of the designator are evaluated once, when the procedure
+    /// is called. There is no reference once the program is halted by an index.
+    pub fn reference(
+        routine: &str,
+        param: &Rc<dyn Node>,
+        mode: PassMode,
+        r#type: Option<&DataType>,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<(ReferenceSymbol, DataType)>, Error> {
+        let name = match param.name() {
+            Ok(Some(name)) => name,
+            _ => {
+                eprintln!(
+                    "[visit] [ProcedureCall] argument of the VAR or OUT parameter of '{}' must be a variable",
+                    routine
+                );
+                return Err(Error::VariableRequired);
+            }
+        };
+
+        let selections = match param.selections(scope.clone()) {
+            Ok(_) if scope.borrow().halted() => return Ok(None),
+            Ok(s) => s,
+            Err(e) => return Err(e),
+        };
+
+        let vs = match scope.borrow().get(&name) {
+            Some(Identifier::Variable(vs)) => vs,
+            Some(_) => {
+                eprintln!(
+                    "[visit] [ProcedureCall] '{}' passed to the VAR or OUT parameter of '{}' is not a variable",
+                    name, routine
+                );
+                return Err(Error::VariableRequired);
+            }
+            None => return Err(Error::VarNotFound),
+        };
+
+        /* The type of the selected component must be the type of the parameter */
+        let heap = scope.borrow().heap();
+        let arg_type = match Heap::component(&heap, vs.clone(), &selections) {
+            Ok(c) => c.r#type(),
+            Err(e) => return Err(e),
+        };
+
+        match r#type {
+            Some(t) if arg_type != *t => {
+                eprintln!(
+                    "[visit] [ProcedureCall] variable '{}' of type {} can not be passed to parameter of type {}",
+                    name,
+                    arg_type,
+                    t
+                );
+                return Err(Error::TypeMismatch);
+            }
+            _ => {}
+        }
+
+        let target = match Scope::defining_scope(scope, &name) {
+            Some(s) => s,
+            None => return Err(Error::VarNotFound),
+        };
+
+        /* The value of the variable on entry is discarded for OUT parameters */
+        if mode == PassMode::Out && selections.is_empty() {
+            let r = target.borrow_mut().set(
+                &name,
+                Identifier::Variable(VariableSymbol::new(
+                    vs.r#type(),
+                    vs.r#type().initial_value(),
+                )),
+            );
+            if let Err(e) = r {
+                return Err(e);
+            }
+        }
+
+        Ok(Some((
+            ReferenceSymbol::new(target, &name, selections),
+            arg_type,
+        )))
+    }
+}
+
 impl Node for ProcedureCall {
     fn r#type(&self) -> NodeType {
         NodeType::ProcedureCall
     }
 
     fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
-        let new_scope = Scope::new(&self.name, Some(scope.clone()), scope.borrow().level() + 1);
-        let new_scope = Rc::new(RefCell::new(new_scope));
-
-        let id = match new_scope.borrow().get(&self.name) {
+        let id = scope.borrow().get(&self.name);
+        let id = match id {
             Some(id) => id,
             None => {
-                println!(
+                eprintln!(
                     "[visit] [ProcedureCall] procedure '{}' not found in scope",
                     self.name
                 );
@@ -41,65 +127,155 @@ impl Node for ProcedureCall {
 
         let ps = match id {
             Identifier::Procedure(ps) => ps,
+            Identifier::Builtin(b) => {
+                return match b.call(&self.parameters, scope) {
+                    Ok(v) => Ok(Info::new(None, NodeType::ProcedureCall, v)),
+                    Err(e) => Err(e),
+                };
+            }
             _ => return Err(Error::InvalidSyntax),
         };
 
+        /* The scope of the call is nested in the scope declaring the procedure */
+        let parent = match ps.scope() {
+            Some(s) => s,
+            None => {
+                eprintln!(
+                    "[visit] [ProcedureCall] scope declaring '{}' no longer exists",
+                    self.name
+                );
+                return Err(Error::ProcedureNotFound);
+            }
+        };
+        let level = parent.borrow().level() + 1;
+        let new_scope = Rc::new(RefCell::new(Scope::new(&self.name, Some(parent), level)));
+
+        /* Define the variable holding the return value of the function */
+        let return_type = match ps.procedure().return_type() {
+            Some(ts) => match ts.resolve(new_scope.clone()) {
+                Ok(t) => Some(t),
+                Err(e) => return Err(e),
+            },
+            None => None,
+        };
+        if let Some(t) = &return_type {
+            if let Err(e) = new_scope.borrow_mut().define(
+                RETURN_VALUE,
+                Identifier::Variable(VariableSymbol::new(t.clone(), t.initial_value())),
+            ) {
+                return Err(e);
+            }
+        }
+
         /* Set the parameters of procedure */
         let mut params = self.parameters.iter();
         for vd in ps.procedure().var_decl_list() {
-            let info = match vd.visit(new_scope.clone()) {
-                Ok(info) => info,
+            let r#type = match vd.type_spec().resolve(new_scope.clone()) {
+                Ok(t) => t,
                 Err(e) => return Err(e),
             };
 
-            let vd_val = match info.value() {
-                Some(v) => v,
-                None => {
-                    println!(
-                        "[visit] [ProcedureCall] the value of variable symbol '{}' is none",
-                        info
-                    );
-                    return Err(Error::InvalidSyntax);
-                }
-            };
-
-            for s in vd_val.value().split(",") {
+            for s in vd.ids() {
                 let param = match params.next() {
                     Some(p) => p,
-                    None => return Err(Error::InvalidSyntax),
+                    None => {
+                        eprintln!(
+                            "[visit] [ProcedureCall] too few arguments passed to '{}'",
+                            self.name
+                        );
+                        return Err(Error::ArgumentCountMismatch);
+                    }
                 };
 
-                let info = match param.visit(new_scope.clone()) {
-                    Ok(info) => info,
-                    Err(e) => return Err(e),
-                };
+                let id = match vd.mode() {
+                    PassMode::Var | PassMode::Out => {
+                        match ProcedureCall::reference(
+                            &self.name,
+                            param,
+                            vd.mode(),
+                            Some(&r#type),
+                            scope.clone(),
+                        ) {
+                            Ok(Some((rs, _))) => Identifier::Reference(rs),
+                            Ok(None) => return Ok(Info::new(None, NodeType::ProcedureCall, None)),
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    PassMode::Value | PassMode::Const => {
+                        /* Arguments are evaluated in the caller's scope */
+                        let param_val = match evaluate(param, scope.clone()) {
+                            Ok(Some(v)) => v,
+                            /* The program halted while the argument was evaluated */
+                            Ok(None) => return Ok(Info::new(None, NodeType::ProcedureCall, None)),
+                            Err(e) => return Err(e),
+                        };
 
-                let param_val = match info.value() {
-                    Some(v) => v,
-                    None => return Err(Error::InvalidSyntax),
-                };
+                        let param_val = match param_val.cast(&r#type) {
+                            Ok(v) => v,
+                            Err(e) => return Err(e),
+                        };
 
-                let r#type = match NumberType::to_number_type(param_val.r#type()) {
-                    Ok(t) => t,
-                    Err(e) => return Err(e),
+                        if vd.mode() == PassMode::Const {
+                            Identifier::Constant(ConstantSymbol::new(r#type.clone(), param_val))
+                        } else {
+                            Identifier::Variable(VariableSymbol::new(
+                                r#type.clone(),
+                                Some(param_val),
+                            ))
+                        }
+                    }
                 };
-                let vs = Identifier::Variable(VariableSymbol::new(
-                    r#type,
-                    Some(param_val.value().to_string()),
-                ));
-                if let Err(e) = new_scope.borrow_mut().set(s, vs) {
+
+                if let Err(e) = new_scope.borrow_mut().define(&s, id) {
                     return Err(e);
                 }
             }
         }
 
-        let r = match ps.procedure().block().visit(new_scope.clone()) {
-            Ok(info) => Ok(Info::new(None, NodeType::ProcedureCall, info.value)),
-            Err(e) => Err(e),
+        if params.next().is_some() {
+            eprintln!(
+                "[visit] [ProcedureCall] too many arguments passed to '{}'",
+                self.name
+            );
+            return Err(Error::ArgumentCountMismatch);
+        }
+
+        if let Err(e) = ps.procedure().block().visit(new_scope.clone()) {
+            return Err(e);
+        }
+
+        if new_scope.borrow().debug() {
+            new_scope.borrow().print();
+        }
+
+        /* EXIT is done once the routine is left, a function of a halted program has no result */
+        if let Some(Signal::Halt(_)) = new_scope.borrow().signal() {
+            return Ok(Info::new(None, NodeType::ProcedureCall, None));
+        }
+
+        let t = match return_type {
+            Some(t) => t,
+            None => return Ok(Info::new(None, NodeType::ProcedureCall, None)),
         };
 
-        new_scope.borrow().print();
+        let result = match new_scope.borrow().find_in_cur_scope(RETURN_VALUE) {
+            Some(Identifier::Variable(vs)) => vs.value(),
+            _ => None,
+        };
+        let result = match result {
+            Some(v) => v,
+            None => {
+                eprintln!(
+                    "[visit] [ProcedureCall] result of function '{}' was not assigned",
+                    self.name
+                );
+                return Err(Error::ResultNotAssigned);
+            }
+        };
 
-        r
+        match result.cast(&t) {
+            Ok(v) => Ok(Info::new(None, NodeType::ProcedureCall, Some(v))),
+            Err(e) => Err(e),
+        }
     }
 }
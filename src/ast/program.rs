@@ -33,7 +33,9 @@ impl Node for Program {
             Err(e) => return Err(e),
         };
 
-        new_scope.borrow().print();
+        if new_scope.borrow().debug() {
+            new_scope.borrow().print();
+        }
 
         Ok(Info::new(Some(self.name.clone()), self.r#type(), val))
     }
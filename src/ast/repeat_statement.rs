@@ -0,0 +1,52 @@
+use super::if_statement::check_condition;
+use super::{Info, Node, NodeType};
+use crate::error::Error;
+use crate::global_scope::Scope;
+use std::{cell::RefCell, rc::Rc};
+
+pub struct Repeat {
+    children: Vec<Rc<dyn Node>>,
+    condition: Rc<dyn Node>,
+}
+
+impl Repeat {
+    pub fn new(children: Vec<Rc<dyn Node>>, condition: Rc<dyn Node>) -> Repeat {
+        Repeat {
+            children,
+            condition,
+        }
+    }
+}
+
+impl Node for Repeat {
+    fn r#type(&self) -> NodeType {
+        NodeType::Repeat
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        loop {
+            /* The body always runs once before the condition is tested */
+            for c in self.children.iter() {
+                if let Err(e) = c.visit(scope.clone()) {
+                    return Err(e);
+                }
+
+                /* CONTINUE skips the rest of the body, BREAK ends the loop */
+                if scope.borrow().signal().is_some() {
+                    if scope.borrow_mut().end_iteration() {
+                        return Ok(Info::new(None, NodeType::Repeat, None));
+                    }
+                    break;
+                }
+            }
+
+            match check_condition(&self.condition, scope.clone()) {
+                Ok(Some(true)) | Ok(None) => break,
+                Ok(Some(false)) => {}
+                Err(e) => return Err(e),
+            };
+        }
+
+        Ok(Info::new(None, NodeType::Repeat, None))
+    }
+}
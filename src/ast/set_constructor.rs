@@ -0,0 +1,93 @@
+use super::{evaluate, Info, Node, NodeType, Value};
+use crate::data_type::{DataType, SetType, EMPTY_SET};
+use crate::error::Error;
+use crate::global_scope::Scope;
+use std::collections::BTreeSet;
+use std::{cell::RefCell, rc::Rc};
+
+/// SetElement is a single element or a range of elements (low..high) of a set constructor.
+pub struct SetElement {
+    low: Rc<dyn Node>,
+    high: Option<Rc<dyn Node>>,
+}
+
+impl SetElement {
+    pub fn new(low: Rc<dyn Node>, high: Option<Rc<dyn Node>>) -> SetElement {
+        SetElement { low, high }
+    }
+}
+
+/// SetConstructor builds a set from its elements, e.g. `[1, 3..5]`, the base type of the set is
+/// the type of its elements.
+pub struct SetConstructor {
+    elements: Vec<SetElement>,
+}
+
+impl SetConstructor {
+    pub fn new(elements: Vec<SetElement>) -> SetConstructor {
+        SetConstructor { elements }
+    }
+}
+
+impl Node for SetConstructor {
+    fn r#type(&self) -> NodeType {
+        NodeType::SetConstructor
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let mut base: Option<String> = None;
+        let mut ordinals = BTreeSet::new();
+
+        for element in self.elements.iter() {
+            let mut bounds = Vec::new();
+            for node in [Some(&element.low), element.high.as_ref()]
+                .into_iter()
+                .flatten()
+            {
+                let val = match evaluate(node, scope.clone()) {
+                    Ok(Some(v)) => v,
+                    Ok(None) => return Ok(Info::new(None, NodeType::SetConstructor, None)),
+                    Err(e) => return Err(e),
+                };
+
+                /* All the elements have the type of the first one */
+                let tag = base.get_or_insert(val.r#type().to_string());
+                if tag.as_str() != val.r#type() {
+                    eprintln!(
+                        "[visit] [{}] element of type {} does not match the set of {}",
+                        self.r#type().as_str(),
+                        val.r#type(),
+                        tag
+                    );
+                    return Err(Error::TypeMismatch);
+                }
+
+                match val.ordinal() {
+                    Ok(n) if (0..=255).contains(&n) => bounds.push(n),
+                    Ok(_) => {
+                        eprintln!(
+                            "[visit] [{}] element {} is out of the range 0..255",
+                            self.r#type().as_str(),
+                            val.value()
+                        );
+                        return Err(Error::OutOfRange);
+                    }
+                    Err(e) => return Err(e),
+                };
+            }
+
+            /* A range with the lower bound greater than the upper bound has no elements */
+            ordinals.extend(bounds[0]..=bounds[bounds.len() - 1]);
+        }
+
+        let val = match base.as_deref().and_then(DataType::from_tag) {
+            Some(t) => match SetType::new(t).value(&ordinals) {
+                Ok(v) => v,
+                Err(e) => return Err(e),
+            },
+            None => Value::set(EMPTY_SET, Vec::new()),
+        };
+
+        Ok(Info::new(None, NodeType::SetConstructor, Some(val)))
+    }
+}
@@ -0,0 +1,41 @@
+use super::type_spec::TypeSpec;
+use super::{Info, Node, NodeType};
+use crate::error::Error;
+use crate::global_scope::{Identifier, Scope, TypeSymbol};
+use std::{cell::RefCell, rc::Rc};
+
+pub struct TypeDecl {
+    name: String,
+    type_spec: Rc<TypeSpec>,
+}
+
+impl TypeDecl {
+    pub fn new(name: &str, type_spec: Rc<TypeSpec>) -> TypeDecl {
+        TypeDecl {
+            name: name.to_string(),
+            type_spec,
+        }
+    }
+}
+
+impl Node for TypeDecl {
+    fn r#type(&self) -> NodeType {
+        NodeType::TypeDecl
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let r#type = match self.type_spec.resolve(scope.clone()) {
+            Ok(t) => t,
+            Err(e) => return Err(e),
+        };
+
+        if let Err(e) = scope.borrow_mut().define(
+            &self.name,
+            Identifier::Type(TypeSymbol::new(&self.name, r#type)),
+        ) {
+            return Err(e);
+        }
+
+        Ok(Info::new(Some(self.name.clone()), self.r#type(), None))
+    }
+}
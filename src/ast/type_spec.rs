@@ -0,0 +1,217 @@
+use super::var_decl::VarDecl;
+use super::{Node, Value};
+use crate::data_type::{
+    ArrayType, DataType, EnumType, PointerType, RecordType, SetType, SubrangeType,
+};
+use crate::error::Error;
+use crate::global_scope::{ConstantSymbol, Identifier, Scope};
+use crate::lexer::lexeme::number::NumberType;
+use crate::lexer::lexeme::Type;
+use std::{cell::RefCell, rc::Rc};
+
+/// TypeSpec is a type as written in the code, it is resolved through the symbol table when the
+/// declaration using it is visited.
+pub enum TypeSpec {
+    Named(String), // The name of a predefined type or a type declared in a TYPE section.
+    Range(Rc<dyn Node>, Rc<dyn Node>), // The constant bounds of a subrange, `low..high`.
+    Enum(Vec<String>), // The names of the constants of an enumerated type, `(a, b, c)`.
+    Array(Vec<Rc<TypeSpec>>, Rc<TypeSpec>), // The index types and the element type.
+    Record(Vec<Rc<VarDecl>>), // The declarations of the fields.
+    Set(Rc<TypeSpec>), // The base type of the set.
+    Pointer(String), // The name of the type the pointer points to, `^name`.
+    String(Option<Rc<dyn Node>>), // The constant maximum length of a STRING[n].
+}
+
+impl TypeSpec {
+    /// name returns the name of the type if it is written as a type identifier, the types of the
+    /// formal parameters are compared by their names.
+    pub fn name(&self) -> Option<String> {
+        match self {
+            TypeSpec::Named(name) => Some(name.to_uppercase()),
+            TypeSpec::String(None) => Some(NumberType::String.r#type().to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn resolve(&self, scope: Rc<RefCell<Scope>>) -> Result<DataType, Error> {
+        match self {
+            TypeSpec::Named(name) => match scope.borrow().get(name) {
+                Some(Identifier::Type(ts)) => Ok(ts.r#type()),
+                Some(_) => {
+                    eprintln!("[resolve] [TypeSpec] '{}' is not a type", name);
+                    Err(Error::TypeNotFound)
+                }
+                None => {
+                    eprintln!("[resolve] [TypeSpec] type '{}' not found", name);
+                    Err(Error::TypeNotFound)
+                }
+            },
+            TypeSpec::Range(low, high) => self.subrange(low, high, scope),
+            TypeSpec::Enum(names) => {
+                let names: Vec<String> = names.iter().map(|n| n.to_uppercase()).collect();
+                let r#type = DataType::Enum(Rc::new(EnumType::new(names.clone())));
+
+                /* The names of an enumerated type are constants of the type in the scope */
+                for name in names.iter() {
+                    let r = scope.borrow_mut().define(
+                        name,
+                        Identifier::Constant(ConstantSymbol::new(
+                            r#type.clone(),
+                            Value::new(&r#type.tag(), name),
+                        )),
+                    );
+                    if let Err(e) = r {
+                        return Err(e);
+                    }
+                }
+
+                Ok(r#type)
+            }
+            TypeSpec::Array(indexes, element) => {
+                let mut r#type = match element.resolve(scope.clone()) {
+                    Ok(t) => t,
+                    Err(e) => return Err(e),
+                };
+
+                /* The last index selects the innermost array */
+                for index in indexes.iter().rev() {
+                    let at = match index.resolve(scope.clone()) {
+                        Ok(t) => match ArrayType::new(t, r#type) {
+                            Ok(at) => at,
+                            Err(e) => return Err(e),
+                        },
+                        Err(e) => return Err(e),
+                    };
+                    r#type = DataType::Array(Rc::new(at));
+                }
+
+                Ok(r#type)
+            }
+            TypeSpec::Set(base) => match base.resolve(scope) {
+                /* The ordinal numbers of the elements of a set are in 0..255 */
+                Ok(t) => match t.bounds() {
+                    Some((low, high)) if low >= 0 && high <= 255 => {
+                        Ok(DataType::Set(Rc::new(SetType::new(t))))
+                    }
+                    _ => {
+                        eprintln!(
+                            "[resolve] [TypeSpec] {} can not be used as the base type of a set",
+                            t
+                        );
+                        Err(Error::TypeMismatch)
+                    }
+                },
+                Err(e) => Err(e),
+            },
+            TypeSpec::Pointer(name) => Ok(DataType::Pointer(Rc::new(PointerType::new(name)))),
+            TypeSpec::String(None) => Ok(DataType::Simple(NumberType::String)),
+            TypeSpec::String(Some(n)) => {
+                let len = match n.visit(scope.clone()) {
+                    Ok(info) => match info.value() {
+                        Some(v) if NumberType::Integer.equal_type(v.r#type()) => {
+                            v.value().parse::<usize>().unwrap_or_default()
+                        }
+                        _ => {
+                            eprintln!(
+                                "[resolve] [TypeSpec] length of the string must be an integer"
+                            );
+                            return Err(Error::TypeMismatch);
+                        }
+                    },
+                    Err(e) => return Err(e),
+                };
+
+                if !(1..=255).contains(&len) {
+                    eprintln!("[resolve] [TypeSpec] length of the string must be in 1..255");
+                    return Err(Error::InvalidSyntax);
+                }
+
+                Ok(DataType::ShortString(len))
+            }
+            TypeSpec::Record(field_decls) => {
+                let mut fields: Vec<(String, DataType)> = Vec::new();
+                for vd in field_decls.iter() {
+                    let r#type = match vd.type_spec().resolve(scope.clone()) {
+                        Ok(t) => t,
+                        Err(e) => return Err(e),
+                    };
+
+                    for name in vd.ids() {
+                        if fields.iter().any(|(n, _)| n.eq_ignore_ascii_case(&name)) {
+                            eprintln!("[resolve] [TypeSpec] field '{}' is redefined", name);
+                            return Err(Error::VarRedefined);
+                        }
+                        fields.push((name, r#type.clone()));
+                    }
+                }
+
+                Ok(DataType::Record(Rc::new(RecordType::new(fields))))
+            }
+        }
+    }
+
+    /// subrange evaluates the constant bounds of a subrange, they must be of the same ordinal type.
+    fn subrange(
+        &self,
+        low: &Rc<dyn Node>,
+        high: &Rc<dyn Node>,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<DataType, Error> {
+        let mut vals = Vec::new();
+        for n in [low, high] {
+            match n.visit(scope.clone()) {
+                Ok(info) => match info.value() {
+                    Some(v) => vals.push(v),
+                    None => {
+                        eprintln!("[resolve] [TypeSpec] value of the bound not found");
+                        return Err(Error::InvalidSyntax);
+                    }
+                },
+                Err(e) => return Err(e),
+            };
+        }
+
+        if vals[0].r#type() != vals[1].r#type() {
+            eprintln!(
+                "[resolve] [TypeSpec] bounds of types {} and {} do not match",
+                vals[0].r#type(),
+                vals[1].r#type()
+            );
+            return Err(Error::TypeMismatch);
+        }
+
+        let host = match DataType::from_tag(vals[0].r#type()) {
+            Some(t) if t.is_ordinal() => t,
+            _ => {
+                eprintln!(
+                    "[resolve] [TypeSpec] bounds of type {} are not ordinal",
+                    vals[0].r#type()
+                );
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        let mut ordinals = Vec::new();
+        for v in vals.iter() {
+            match v.ordinal() {
+                Ok(n) => ordinals.push(n),
+                Err(e) => return Err(e),
+            };
+        }
+
+        if ordinals[0] > ordinals[1] {
+            eprintln!(
+                "[resolve] [TypeSpec] lower bound {} is greater than upper bound {}",
+                vals[0].value(),
+                vals[1].value()
+            );
+            return Err(Error::InvalidSyntax);
+        }
+
+        Ok(DataType::Subrange(Rc::new(SubrangeType::new(
+            host,
+            ordinals[0],
+            ordinals[1],
+        ))))
+    }
+}
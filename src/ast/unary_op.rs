@@ -1,4 +1,4 @@
-use super::{Info, Node, NodeType, Value};
+use super::{evaluate, Info, Node, NodeType, Value};
 use crate::error::Error;
 use crate::global_scope::Scope;
 use crate::lexer::lexeme::number::NumberType;
@@ -8,7 +8,7 @@ use std::str::FromStr;
 use std::{cell::RefCell, rc::Rc};
 
 pub struct UnaryOp {
-    r#type: String, // add or sub
+    r#type: String, // add, sub or not
     node: Rc<dyn Node>,
 }
 
@@ -30,7 +30,7 @@ where
     let num = match val.parse::<T>() {
         Ok(n) => n,
         Err(e) => {
-            println!("[visit] [UnaryOp] parse num '{}' failed, error: {}", val, e);
+            eprintln!("[visit] [UnaryOp] parse num '{}' failed, error: {}", val, e);
             return Err(Error::InvalidSyntax);
         }
     };
@@ -52,19 +52,38 @@ impl Node for UnaryOp {
     }
 
     fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
-        let info = match self.node.visit(scope.clone()) {
-            Ok(info) => match info.value {
-                Some(val) => val,
-                None => {
-                    println!("[visit] val not found in UnaryOp");
-                    return Err(Error::InvalidSyntax);
-                }
-            },
+        let info = match evaluate(&self.node, scope.clone()) {
+            Ok(Some(val)) => val,
+            Ok(None) => return Ok(Info::new(None, NodeType::UnaryOp, None)),
             Err(e) => return Err(e),
         };
 
+        /* NOT only applies to booleans, '+' and '-' only apply to numbers */
+        if Op::Not.equal_type(&self.r#type) != NumberType::Boolean.equal_type(&info.r#type) {
+            eprintln!(
+                "[visit] [{}] op '{}' can not be applied to '{}'",
+                self.r#type().as_str(),
+                self.r#type,
+                info.r#type
+            );
+            return Err(Error::TypeMismatch);
+        }
+
         let val: Value;
-        if NumberType::Real.equal_type(info.r#type) {
+        if NumberType::Boolean.equal_type(&info.r#type) {
+            let r = match info.value.parse::<bool>() {
+                Ok(b) => !b,
+                Err(e) => {
+                    eprintln!(
+                        "[visit] [UnaryOp] parse boolean '{}' failed, error: {}",
+                        info.value, e
+                    );
+                    return Err(Error::InvalidSyntax);
+                }
+            };
+
+            val = Value::new(NumberType::Boolean.r#type(), &r.to_string());
+        } else if NumberType::Real.equal_type(info.r#type) {
             let r = match cal::<f32>(&info.value, &self.r#type) {
                 Ok(r) => r,
                 Err(e) => return Err(e),
@@ -1,19 +1,44 @@
-use super::{Info, Node, NodeType, Value};
+use super::procedure_call::ProcedureCall;
+use super::{evaluate, Info, Node, NodeType};
+use crate::data_type::Selection;
 use crate::error::Error;
-use crate::global_scope::{Identifier, Scope};
-use crate::lexer::lexeme::Type;
+use crate::global_scope::{Heap, Identifier, Scope, VariableSymbol};
 use std::{cell::RefCell, rc::Rc};
 
+/// Selector selects a component of a structured variable.
+pub enum Selector {
+    Index(Rc<dyn Node>), // The index expression of an array element, `a[i]`.
+    Field(String),       // The name of a record field, `r.f`.
+    Deref,               // The variable a pointer points to, `p^`.
+}
+
+/// Var is a designator, the name of a variable followed by the selectors of its components,
+/// e.g. `a[i, j].f^`. It is shared by expressions, assignments and VAR parameters.
 pub struct Var {
     name: String,
+    selectors: Vec<Selector>,
 }
 
 impl Var {
     pub fn new(name: &str) -> Var {
         Var {
             name: name.to_string(),
+            selectors: Vec::new(),
+        }
+    }
+
+    /// designator returns the variable with the selectors, `a[i, j]` and `a[i][j]` both have the
+    /// index selectors `i` and `j`.
+    pub fn designator(name: &str, selectors: Vec<Selector>) -> Var {
+        Var {
+            name: name.to_string(),
+            selectors,
         }
     }
+
+    pub fn selectors(&self) -> &[Selector] {
+        &self.selectors
+    }
 }
 
 impl Node for Var {
@@ -25,6 +50,32 @@ impl Node for Var {
         return Ok(Some(self.name.clone()));
     }
 
+    /// selections evaluates the index expressions from left to right, they stop at the index
+    /// whose evaluation halted the program, so the callers check Scope::halted.
+    fn selections(&self, scope: Rc<RefCell<Scope>>) -> Result<Vec<Selection>, Error> {
+        let mut selections = Vec::new();
+        for selector in self.selectors.iter() {
+            let index = match selector {
+                Selector::Index(index) => index,
+                Selector::Field(name) => {
+                    selections.push(Selection::Field(name.clone()));
+                    continue;
+                }
+                Selector::Deref => {
+                    selections.push(Selection::Deref);
+                    continue;
+                }
+            };
+
+            match evaluate(index, scope.clone()) {
+                Ok(Some(v)) => selections.push(Selection::Index(v)),
+                Ok(None) => return Ok(selections),
+                Err(e) => return Err(e),
+            };
+        }
+        Ok(selections)
+    }
+
     fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
         let id = match scope.borrow().get(&self.name) {
             Some(id) => id,
@@ -33,14 +84,36 @@ impl Node for Var {
 
         let vs = match id {
             Identifier::Variable(vs) => vs,
+            Identifier::Constant(cs) => VariableSymbol::new(cs.r#type(), Some(cs.value())),
+            /* A function without parameters is called by its name */
+            Identifier::Procedure(ps) if ps.procedure().return_type().is_some() => {
+                return ProcedureCall::new(&self.name, Vec::new()).visit(scope);
+            }
             _ => return Err(Error::InvalidSyntax),
         };
 
-        let value = match vs.value() {
-            Some(v) => Some(Value::new(vs.r#type().r#type(), &v)),
-            None => return Err(Error::InvalidSyntax),
+        let selections = match self.selections(scope.clone()) {
+            Ok(_) if scope.borrow().halted() => {
+                return Ok(Info::new(Some(self.name.clone()), NodeType::Var, None))
+            }
+            Ok(s) => s,
+            Err(e) => return Err(e),
+        };
+
+        /* Pointers are followed through the heap */
+        let heap = scope.borrow().heap();
+        let value = match Heap::component(&heap, vs, &selections) {
+            Ok(vs) => match vs.value() {
+                Some(v) => v,
+                None => return Err(Error::InvalidSyntax),
+            },
+            Err(e) => return Err(e),
         };
 
-        Ok(Info::new(Some(self.name.clone()), NodeType::Var, value))
+        Ok(Info::new(
+            Some(self.name.clone()),
+            NodeType::Var,
+            Some(value),
+        ))
     }
 }
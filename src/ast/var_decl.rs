@@ -1,18 +1,48 @@
+use super::type_spec::TypeSpec;
 use super::{Info, Node, NodeType, Value};
 use crate::error::Error;
 use crate::global_scope::{Identifier, Scope, VariableSymbol};
-use crate::lexer::lexeme::number::NumberType;
-use crate::lexer::lexeme::Type;
 use std::{cell::RefCell, rc::Rc};
 
+/// PassMode is how an argument is passed to a formal parameter.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PassMode {
+    Value, // The argument is copied.
+    Var,   // The argument is passed by reference.
+    Const, // The argument is copied and can not be assigned.
+    Out,   // The argument is passed by reference and its value on entry is discarded.
+}
+
 pub struct VarDecl {
     ids: Vec<String>,
-    r#type: NumberType,
+    r#type: Rc<TypeSpec>,
+    mode: PassMode,
 }
 
 impl VarDecl {
-    pub fn new(ids: Vec<String>, r#type: NumberType) -> VarDecl {
-        VarDecl { ids, r#type }
+    pub fn new(ids: Vec<String>, r#type: Rc<TypeSpec>) -> VarDecl {
+        VarDecl {
+            ids,
+            r#type,
+            mode: PassMode::Value,
+        }
+    }
+
+    /// parameter returns the declaration of formal parameters passed with the mode.
+    pub fn parameter(ids: Vec<String>, r#type: Rc<TypeSpec>, mode: PassMode) -> VarDecl {
+        VarDecl { ids, r#type, mode }
+    }
+
+    pub fn ids(&self) -> Vec<String> {
+        self.ids.clone()
+    }
+
+    pub fn type_spec(&self) -> Rc<TypeSpec> {
+        self.r#type.clone()
+    }
+
+    pub fn mode(&self) -> PassMode {
+        self.mode
     }
 }
 
@@ -22,10 +52,15 @@ impl Node for VarDecl {
     }
 
     fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let r#type = match self.r#type.resolve(scope.clone()) {
+            Ok(t) => t,
+            Err(e) => return Err(e),
+        };
+
         for key in self.ids.iter() {
             if let Err(e) = scope.borrow_mut().define(
                 key,
-                Identifier::Variable(VariableSymbol::new(self.r#type, None)),
+                Identifier::Variable(VariableSymbol::new(r#type.clone(), r#type.initial_value())),
             ) {
                 return Err(e);
             }
@@ -34,7 +69,7 @@ impl Node for VarDecl {
         Ok(Info::new(
             None,
             self.r#type(),
-            Some(Value::new(self.r#type.r#type(), &self.ids.join(","))),
+            Some(Value::new(&r#type.to_string(), &self.ids.join(","))),
         ))
     }
 }
@@ -0,0 +1,44 @@
+use super::if_statement::check_condition;
+use super::{Info, Node, NodeType};
+use crate::error::Error;
+use crate::global_scope::Scope;
+use std::{cell::RefCell, rc::Rc};
+
+pub struct While {
+    condition: Rc<dyn Node>,
+    body: Rc<dyn Node>,
+}
+
+impl While {
+    pub fn new(condition: Rc<dyn Node>, body: Rc<dyn Node>) -> While {
+        While { condition, body }
+    }
+}
+
+impl Node for While {
+    fn r#type(&self) -> NodeType {
+        NodeType::While
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        loop {
+            /* The condition is evaluated again before every iteration */
+            match check_condition(&self.condition, scope.clone()) {
+                Ok(Some(true)) => {}
+                Ok(Some(false)) | Ok(None) => break,
+                Err(e) => return Err(e),
+            };
+
+            if let Err(e) = self.body.visit(scope.clone()) {
+                return Err(e);
+            }
+
+            /* BREAK ends the loop, and so does a transfer of control out of it */
+            if scope.borrow_mut().end_iteration() {
+                break;
+            }
+        }
+
+        Ok(Info::new(None, NodeType::While, None))
+    }
+}
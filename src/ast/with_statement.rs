@@ -0,0 +1,122 @@
+use super::var::Var;
+use super::{Info, Node, NodeType};
+use crate::data_type::{DataType, Selection};
+use crate::error::Error;
+use crate::global_scope::{Heap, Identifier, ReferenceSymbol, Scope};
+use std::{cell::RefCell, rc::Rc};
+
+/// With is `WITH record, ... DO statement`. Each record opens a scope nested in the scope of the
+/// statement that defines its fields, so an unqualified name is a field of the innermost record
+/// having it before it is a variable. `WITH a, b DO s` is `WITH a DO WITH b DO s`.
+pub struct With {
+    records: Vec<Rc<Var>>,
+    body: Rc<dyn Node>,
+}
+
+impl With {
+    pub fn new(records: Vec<Rc<Var>>, body: Rc<dyn Node>) -> With {
+        With { records, body }
+    }
+
+    /// open returns the scope of the fields of the record. The designator of the record is
+    /// evaluated once, the fields refer to the record it selects even if its indexes change.
+    /// There is no scope once the program is halted while evaluating the designator.
+    fn open(
+        &self,
+        record: &Rc<Var>,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<Rc<RefCell<Scope>>>, Error> {
+        let name = match record.name() {
+            Ok(Some(name)) => name,
+            _ => return Err(Error::VarNotFound),
+        };
+
+        let vs = match scope.borrow().get(&name) {
+            Some(Identifier::Variable(vs)) => vs,
+            Some(_) => {
+                eprintln!(
+                    "[visit] [{}] '{}' is not a record variable",
+                    self.r#type().as_str(),
+                    name
+                );
+                return Err(Error::VariableRequired);
+            }
+            None => return Err(Error::VarNotFound),
+        };
+
+        let selections = match record.selections(scope.clone()) {
+            Ok(_) if scope.borrow().halted() => return Ok(None),
+            Ok(s) => s,
+            Err(e) => return Err(e),
+        };
+
+        let heap = scope.borrow().heap();
+        let rt = match Heap::component(&heap, vs, &selections) {
+            Ok(c) => match c.r#type() {
+                DataType::Record(rt) => rt,
+                t => {
+                    eprintln!(
+                        "[visit] [{}] '{}' of type {} is not a record",
+                        self.r#type().as_str(),
+                        name,
+                        t
+                    );
+                    return Err(Error::TypeMismatch);
+                }
+            },
+            Err(e) => return Err(e),
+        };
+
+        let target = match Scope::defining_scope(scope.clone(), &name) {
+            Some(s) => s,
+            None => return Err(Error::VarNotFound),
+        };
+
+        /* The scope of the fields is part of the scope of the statement, not a new call */
+        let level = scope.borrow().level();
+        let mut fields = Scope::new(self.r#type().as_str(), Some(scope), level);
+        for (field, _) in rt.fields() {
+            let mut path = selections.clone();
+            path.push(Selection::Field(field.clone()));
+
+            let r = fields.define(
+                field,
+                Identifier::Reference(ReferenceSymbol::new(target.clone(), &name, path)),
+            );
+            if let Err(e) = r {
+                return Err(e);
+            }
+        }
+
+        Ok(Some(Rc::new(RefCell::new(fields))))
+    }
+}
+
+impl Node for With {
+    fn r#type(&self) -> NodeType {
+        NodeType::With
+    }
+
+    fn visit(&self, scope: Rc<RefCell<Scope>>) -> Result<Info, Error> {
+        let mut inner = scope.clone();
+        for record in self.records.iter() {
+            inner = match self.open(record, inner) {
+                Ok(Some(s)) => s,
+                Ok(None) => return Ok(Info::new(None, NodeType::With, None)),
+                Err(e) => return Err(e),
+            };
+        }
+
+        if let Err(e) = self.body.visit(inner.clone()) {
+            return Err(e);
+        }
+
+        /* A GOTO out of the statement is handled by the statements enclosing it */
+        let signal = inner.borrow().signal();
+        if let Some(s) = signal {
+            scope.borrow_mut().raise(s);
+        }
+
+        Ok(Info::new(None, NodeType::With, None))
+    }
+}
@@ -0,0 +1,635 @@
+use crate::ast::procedure_call::ProcedureCall;
+use crate::ast::var_decl::PassMode;
+use crate::ast::{evaluate, Node, NodeType, Value};
+use crate::console;
+use crate::data_type::DataType;
+use crate::error::Error;
+use crate::global_scope::{Heap, Identifier, Scope, VariableSymbol};
+use crate::lexer::lexeme::number::NumberType;
+use crate::lexer::lexeme::Type;
+use std::fmt::{self, Display};
+use std::{cell::RefCell, rc::Rc};
+
+/// Builtin is a predefined procedure or function. The builtins are not defined in any scope,
+/// a name not found in the scopes is looked up here, so a routine declared with the same name
+/// hides the builtin.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Builtin {
+    New,
+    Dispose,
+    Write,
+    WriteLn,
+    Read,
+    ReadLn,
+    Ord,
+    Chr,
+    Succ,
+    Pred,
+    Odd,
+    Low,
+    High,
+    Inc,
+    Dec,
+    Abs,
+    Sqr,
+    Sqrt,
+    Sin,
+    Cos,
+    ArcTan,
+    Ln,
+    Exp,
+    Trunc,
+    Round,
+    Frac,
+    Int,
+}
+
+impl Builtin {
+    const ALL: [Builtin; 27] = [
+        Builtin::New,
+        Builtin::Dispose,
+        Builtin::Write,
+        Builtin::WriteLn,
+        Builtin::Read,
+        Builtin::ReadLn,
+        Builtin::Ord,
+        Builtin::Chr,
+        Builtin::Succ,
+        Builtin::Pred,
+        Builtin::Odd,
+        Builtin::Low,
+        Builtin::High,
+        Builtin::Inc,
+        Builtin::Dec,
+        Builtin::Abs,
+        Builtin::Sqr,
+        Builtin::Sqrt,
+        Builtin::Sin,
+        Builtin::Cos,
+        Builtin::ArcTan,
+        Builtin::Ln,
+        Builtin::Exp,
+        Builtin::Trunc,
+        Builtin::Round,
+        Builtin::Frac,
+        Builtin::Int,
+    ];
+
+    /// lookup returns the builtin named `name`, names are case-insensitive.
+    pub fn lookup(name: &str) -> Option<Builtin> {
+        Builtin::ALL
+            .into_iter()
+            .find(|b| b.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Builtin::New => "NEW",
+            Builtin::Dispose => "DISPOSE",
+            Builtin::Write => "WRITE",
+            Builtin::WriteLn => "WRITELN",
+            Builtin::Read => "READ",
+            Builtin::ReadLn => "READLN",
+            Builtin::Ord => "ORD",
+            Builtin::Chr => "CHR",
+            Builtin::Succ => "SUCC",
+            Builtin::Pred => "PRED",
+            Builtin::Odd => "ODD",
+            Builtin::Low => "LOW",
+            Builtin::High => "HIGH",
+            Builtin::Inc => "INC",
+            Builtin::Dec => "DEC",
+            Builtin::Abs => "ABS",
+            Builtin::Sqr => "SQR",
+            Builtin::Sqrt => "SQRT",
+            Builtin::Sin => "SIN",
+            Builtin::Cos => "COS",
+            Builtin::ArcTan => "ARCTAN",
+            Builtin::Ln => "LN",
+            Builtin::Exp => "EXP",
+            Builtin::Trunc => "TRUNC",
+            Builtin::Round => "ROUND",
+            Builtin::Frac => "FRAC",
+            Builtin::Int => "INT",
+        }
+    }
+
+    /// arity returns the smallest and the largest number of arguments, Write and Read take any
+    /// number of arguments.
+    pub fn arity(&self) -> (usize, Option<usize>) {
+        match self {
+            Builtin::Write | Builtin::WriteLn | Builtin::Read | Builtin::ReadLn => (0, None),
+            Builtin::Inc | Builtin::Dec => (1, Some(2)),
+            _ => (1, Some(1)),
+        }
+    }
+
+    /// mode returns how the argument at `position` is passed, the arguments passed as VAR must be
+    /// variables.
+    pub fn mode(&self, position: usize) -> PassMode {
+        match self {
+            Builtin::New | Builtin::Dispose | Builtin::Read | Builtin::ReadLn => PassMode::Var,
+            Builtin::Inc | Builtin::Dec if position == 0 => PassMode::Var,
+            _ => PassMode::Value,
+        }
+    }
+
+    /// check checks the number of the arguments and that variables are passed as VAR, it is done
+    /// when the call is parsed.
+    pub fn check(&self, args: &[Rc<dyn Node>]) -> Result<(), Error> {
+        let (min, max) = self.arity();
+        if args.len() < min || max.is_some_and(|max| args.len() > max) {
+            eprintln!(
+                "[builtin] [check] '{}' does not take {} arguments",
+                self.name(),
+                args.len()
+            );
+            return Err(Error::ArgumentCountMismatch);
+        }
+
+        for (i, arg) in args.iter().enumerate() {
+            if self.mode(i) == PassMode::Var && !matches!(arg.r#type(), NodeType::Var) {
+                eprintln!(
+                    "[builtin] [check] argument {} of '{}' must be a variable",
+                    i + 1,
+                    self.name()
+                );
+                return Err(Error::VariableRequired);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// call calls the builtin with the arguments of the call, a function returns its value.
+    pub fn call(
+        &self,
+        args: &[Rc<dyn Node>],
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<Value>, Error> {
+        if let Err(e) = self.check(args) {
+            return Err(e);
+        }
+
+        match self {
+            Builtin::New | Builtin::Dispose => self.heap_routine(&args[0], scope),
+            Builtin::Write | Builtin::WriteLn => self.write_routine(args, scope),
+            Builtin::Read | Builtin::ReadLn => self.read_routine(args, scope),
+            Builtin::Low | Builtin::High => self.bound(&args[0], scope),
+            Builtin::Inc | Builtin::Dec => self.step(args, scope),
+            _ => {
+                let val = match evaluate(&args[0], scope) {
+                    Ok(Some(v)) => v,
+                    Ok(None) => return Ok(None),
+                    Err(e) => return Err(e),
+                };
+                let val = match self {
+                    Builtin::Ord | Builtin::Chr | Builtin::Succ | Builtin::Pred | Builtin::Odd => {
+                        self.ordinal_function(&val)
+                    }
+                    _ => self.math_function(&val),
+                };
+                match val {
+                    Ok(v) => Ok(Some(v)),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+
+    /// ordinal_function calls Ord, Chr, Succ, Pred or Odd.
+    fn ordinal_function(&self, val: &Value) -> Result<Value, Error> {
+        let ordinal = match val.ordinal() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+        let integer = || Value::new(NumberType::Integer.r#type(), &ordinal.to_string());
+
+        match self {
+            Builtin::Ord => Ok(integer()),
+            Builtin::Chr => match self.integer(val) {
+                Ok(_) => shift(&DataType::Simple(NumberType::Char), &integer(), 0),
+                Err(e) => Err(e),
+            },
+            Builtin::Odd => match self.integer(val) {
+                Ok(n) => Value::from_ordinal(NumberType::Boolean, n.rem_euclid(2)),
+                Err(e) => Err(e),
+            },
+            Builtin::Succ | Builtin::Pred => {
+                let r#type = match DataType::from_tag(val.r#type()) {
+                    Some(t) => t,
+                    None => return Err(Error::TypeMismatch),
+                };
+                let by = if *self == Builtin::Succ { 1 } else { -1 };
+                shift(&r#type, val, by)
+            }
+            _ => Err(Error::InvalidSyntax),
+        }
+    }
+
+    /// math_function calls Abs, Sqr, Sqrt, Sin, Cos, ArcTan, Ln, Exp, Trunc, Round, Frac or Int.
+    /// Like the arithmetic operators, Abs and Sqr of an INTEGER are INTEGER, Trunc and Round
+    /// always return an INTEGER and the others a REAL.
+    fn math_function(&self, val: &Value) -> Result<Value, Error> {
+        let integer = NumberType::Integer.equal_type(val.r#type());
+        if !integer && !NumberType::Real.equal_type(val.r#type()) {
+            eprintln!(
+                "[builtin] [math_function] '{}' takes an INTEGER or a REAL, not {}",
+                self.name(),
+                val.r#type()
+            );
+            return Err(Error::TypeMismatch);
+        }
+
+        if integer && (*self == Builtin::Abs || *self == Builtin::Sqr) {
+            let n = match val.ordinal() {
+                Ok(n) => n,
+                Err(e) => return Err(e),
+            };
+            let r = if *self == Builtin::Abs {
+                n.checked_abs()
+            } else {
+                n.checked_mul(n)
+            };
+            return match r {
+                Some(r) => Ok(Value::new(NumberType::Integer.r#type(), &r.to_string())),
+                None => {
+                    eprintln!(
+                        "[builtin] [math_function] {}({}) is out of the range of INTEGER",
+                        self.name(),
+                        n
+                    );
+                    Err(Error::OutOfRange)
+                }
+            };
+        }
+
+        let x = match val.value().parse::<f32>() {
+            Ok(x) => x,
+            Err(e) => {
+                eprintln!(
+                    "[builtin] [math_function] parse '{}' failed, error: {}",
+                    val.value(),
+                    e
+                );
+                return Err(Error::InvalidSyntax);
+            }
+        };
+
+        if (*self == Builtin::Sqrt && x < 0.0) || (*self == Builtin::Ln && x <= 0.0) {
+            eprintln!(
+                "[builtin] [math_function] {}({}) is not defined",
+                self.name(),
+                val.value()
+            );
+            return Err(Error::OutOfDomain);
+        }
+
+        let r = match self {
+            Builtin::Abs => x.abs(),
+            Builtin::Sqr => x * x,
+            Builtin::Sqrt => x.sqrt(),
+            Builtin::Sin => x.sin(),
+            Builtin::Cos => x.cos(),
+            Builtin::ArcTan => x.atan(),
+            Builtin::Ln => x.ln(),
+            Builtin::Exp => x.exp(),
+            Builtin::Trunc | Builtin::Int => x.trunc(),
+            /* Halves are rounded away from zero */
+            Builtin::Round => x.round(),
+            Builtin::Frac => x.fract(),
+            _ => return Err(Error::InvalidSyntax),
+        };
+
+        let in_range = match self {
+            Builtin::Trunc | Builtin::Round => r >= i32::MIN as f32 && r < -(i32::MIN as f32),
+            _ => r.is_finite(),
+        };
+        if !in_range {
+            eprintln!(
+                "[builtin] [math_function] {}({}) is out of range",
+                self.name(),
+                val.value()
+            );
+            return Err(Error::OutOfRange);
+        }
+
+        match self {
+            Builtin::Trunc | Builtin::Round => Ok(Value::new(
+                NumberType::Integer.r#type(),
+                &(r as i32).to_string(),
+            )),
+            _ => Ok(Value::new(NumberType::Real.r#type(), &r.to_string())),
+        }
+    }
+
+    /// integer returns the number of an INTEGER argument.
+    fn integer(&self, val: &Value) -> Result<i32, Error> {
+        if val.r#type() != NumberType::Integer.r#type() {
+            eprintln!(
+                "[builtin] [integer] '{}' takes an INTEGER, not {}",
+                self.name(),
+                val.r#type()
+            );
+            return Err(Error::TypeMismatch);
+        }
+        val.ordinal()
+    }
+
+    /// bound calls Low or High, which return the smallest or the largest value of an ordinal
+    /// type or of the index type of an array. The argument is a type or a variable of the type.
+    fn bound(&self, arg: &Rc<dyn Node>, scope: Rc<RefCell<Scope>>) -> Result<Option<Value>, Error> {
+        let name = match arg.name() {
+            Ok(Some(name)) => name,
+            _ => {
+                eprintln!(
+                    "[builtin] [bound] argument of '{}' must be a type or a variable",
+                    self.name()
+                );
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        let id = scope.borrow().get(&name);
+        let r#type = match id {
+            Some(Identifier::Type(ts)) => ts.r#type(),
+            Some(Identifier::Variable(vs)) => {
+                let selections = match arg.selections(scope.clone()) {
+                    Ok(_) if scope.borrow().halted() => return Ok(None),
+                    Ok(s) => s,
+                    Err(e) => return Err(e),
+                };
+                let heap = scope.borrow().heap();
+                match Heap::component(&heap, vs, &selections) {
+                    Ok(c) => c.r#type(),
+                    Err(e) => return Err(e),
+                }
+            }
+            Some(Identifier::Constant(cs)) => cs.r#type(),
+            _ => return Err(Error::VarNotFound),
+        };
+
+        let r#type = match r#type {
+            DataType::Array(at) => at.index(),
+            t => t,
+        };
+        let (low, high) = match r#type.bounds() {
+            Some(b) => b,
+            None => {
+                eprintln!(
+                    "[builtin] [bound] '{}' can not be applied to {}",
+                    self.name(),
+                    r#type
+                );
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        let ordinal = if *self == Builtin::Low { low } else { high };
+        match r#type.value_at(ordinal) {
+            Ok(v) => Ok(Some(v)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// step calls Inc or Dec, which add or subtract the amount, 1 by default, to the ordinal
+    /// number of the variable in place.
+    fn step(
+        &self,
+        args: &[Rc<dyn Node>],
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<Value>, Error> {
+        let amount = match args.get(1) {
+            Some(arg) => match evaluate(arg, scope.clone()) {
+                Ok(Some(v)) => match self.integer(&v) {
+                    Ok(n) => n,
+                    Err(e) => return Err(e),
+                },
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(e),
+            },
+            None => 1,
+        };
+        let amount = if *self == Builtin::Inc {
+            Some(amount)
+        } else {
+            amount.checked_neg()
+        };
+
+        let (rs, r#type) =
+            match ProcedureCall::reference(self.name(), &args[0], PassMode::Var, None, scope) {
+                Ok(Some(r)) => r,
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+        let val = match rs.get() {
+            Some(Identifier::Variable(vs)) => vs.value(),
+            _ => None,
+        };
+        let val = match val {
+            Some(v) => v,
+            None => {
+                eprintln!(
+                    "[builtin] [step] '{}' can not be applied to an undefined variable",
+                    self.name()
+                );
+                return Err(Error::InvalidSyntax);
+            }
+        };
+
+        let val = match amount {
+            Some(n) => shift(&r#type, &val, n),
+            None => Err(Error::OutOfRange),
+        };
+        let val = match val {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+
+        match rs.set(Identifier::Variable(VariableSymbol::new(r#type, Some(val)))) {
+            Ok(_) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// heap_routine calls New, which allocates a variable of the type the pointer points to, or
+    /// Dispose, which releases the variable the pointer points to.
+    fn heap_routine(
+        &self,
+        arg: &Rc<dyn Node>,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<Value>, Error> {
+        let (rs, r#type) =
+            match ProcedureCall::reference(self.name(), arg, PassMode::Var, None, scope.clone()) {
+                Ok(Some(r)) => r,
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+        let pt = match &r#type {
+            DataType::Pointer(pt) => pt.clone(),
+            t => {
+                eprintln!(
+                    "[builtin] [heap_routine] '{}' can not be applied to a variable of type {}",
+                    self.name(),
+                    t
+                );
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        let heap = scope.borrow().heap();
+        if *self == Builtin::Dispose {
+            let pointer = match rs.get() {
+                Some(Identifier::Variable(vs)) => vs,
+                _ => return Err(Error::VarNotFound),
+            };
+            let address = match Heap::dereference(&heap, &pointer) {
+                Ok((address, _)) => address,
+                Err(e) => return Err(e),
+            };
+            return match heap.borrow_mut().dispose(address) {
+                Ok(_) => Ok(None),
+                Err(e) => Err(e),
+            };
+        }
+
+        /* The target type is looked up now, it may be declared after the pointer type */
+        let target = match scope.borrow().get(pt.target()) {
+            Some(Identifier::Type(ts)) => ts.r#type(),
+            _ => {
+                eprintln!(
+                    "[builtin] [heap_routine] type '{}' of the pointer not found",
+                    pt.target()
+                );
+                return Err(Error::TypeNotFound);
+            }
+        };
+        let address = heap
+            .borrow_mut()
+            .allocate(Identifier::Variable(VariableSymbol::new(
+                target.clone(),
+                target.initial_value(),
+            )));
+
+        let pointer = Value::new(&r#type.tag(), &address.to_string());
+        match rs.set(Identifier::Variable(VariableSymbol::new(
+            r#type,
+            Some(pointer),
+        ))) {
+            Ok(_) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// write_routine calls Write or WriteLn, which write the text of the arguments one after the
+    /// other, WriteLn ends the line.
+    fn write_routine(
+        &self,
+        args: &[Rc<dyn Node>],
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<Value>, Error> {
+        let console = scope.borrow().console();
+        for arg in args.iter() {
+            let val = match evaluate(arg, scope.clone()) {
+                Ok(Some(v)) => v,
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+
+            let text = match console::format(&val, None, None) {
+                Ok(t) => t,
+                Err(e) => return Err(e),
+            };
+            if let Err(e) = console.borrow_mut().write(&text) {
+                return Err(e);
+            }
+        }
+
+        if *self == Builtin::WriteLn {
+            if let Err(e) = console.borrow_mut().write("\n") {
+                return Err(e);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// read_routine calls Read or ReadLn, which read a value of the type of each variable from
+    /// the input, ReadLn then skips the rest of the line.
+    fn read_routine(
+        &self,
+        args: &[Rc<dyn Node>],
+        scope: Rc<RefCell<Scope>>,
+    ) -> Result<Option<Value>, Error> {
+        let console = scope.borrow().console();
+        for arg in args.iter() {
+            let (rs, r#type) = match ProcedureCall::reference(
+                self.name(),
+                arg,
+                PassMode::Var,
+                None,
+                scope.clone(),
+            ) {
+                Ok(Some(r)) => r,
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+
+            let val = console.borrow_mut().read(&r#type);
+            let val = match val {
+                Ok(v) => match v.cast(&r#type) {
+                    Ok(v) => v,
+                    Err(e) => return Err(e),
+                },
+                Err(e) => return Err(e),
+            };
+
+            if let Err(e) = rs.set(Identifier::Variable(VariableSymbol::new(r#type, Some(val)))) {
+                return Err(e);
+            }
+        }
+
+        if *self == Builtin::ReadLn {
+            if let Err(e) = console.borrow_mut().skip_line() {
+                return Err(e);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl Display for Builtin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{name: {}}}", self.name())
+    }
+}
+
+/// shift returns the value of the ordinal type whose ordinal number is `by` after the one of the
+/// value, the result must be in the bounds of the type.
+fn shift(r#type: &DataType, val: &Value, by: i32) -> Result<Value, Error> {
+    let (low, high) = match r#type.bounds() {
+        Some(b) => b,
+        None => {
+            eprintln!("[builtin] [shift] {} is not an ordinal type", r#type);
+            return Err(Error::TypeMismatch);
+        }
+    };
+
+    let ordinal = match val.ordinal() {
+        Ok(n) => n.checked_add(by),
+        Err(e) => return Err(e),
+    };
+    match ordinal {
+        Some(n) if n >= low && n <= high => r#type.value_at(n),
+        _ => {
+            eprintln!(
+                "[builtin] [shift] {} moved by {} is out of the range of {}",
+                val.value(),
+                by,
+                r#type
+            );
+            Err(Error::OutOfRange)
+        }
+    }
+}
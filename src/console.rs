@@ -0,0 +1,267 @@
+use crate::ast::Value;
+use crate::data_type::DataType;
+use crate::error::Error;
+use crate::lexer::lexeme::number::NumberType;
+use crate::lexer::lexeme::Type;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+
+/// REAL_DIGITS is the number of digits after the decimal point of a REAL written without a field
+/// width, e.g. ` 3.1400000000E+00`.
+const REAL_DIGITS: usize = 10;
+
+/// REAL_OVERHEAD is the number of characters of a REAL in floating-point notation besides the
+/// digits after the decimal point: the sign, the first digit, the point and the exponent.
+const REAL_OVERHEAD: usize = 7;
+
+/// Console is the standard input and output of the program. Read and ReadLn take the values from
+/// the line being read, Write and WriteLn write the text of the values.
+pub struct Console {
+    input: Box<dyn BufRead>,
+    output: Box<dyn Write>,
+    line: Option<VecDeque<char>>, // The rest of the line being read, with its line break.
+}
+
+impl Console {
+    pub fn new(input: Box<dyn BufRead>, output: Box<dyn Write>) -> Console {
+        Console {
+            input,
+            output,
+            line: None,
+        }
+    }
+
+    /// stdio returns the console reading the standard input and writing the standard output.
+    pub fn stdio() -> Console {
+        Console::new(
+            Box::new(io::BufReader::new(io::stdin())),
+            Box::new(io::stdout()),
+        )
+    }
+
+    /// write writes the text to the output, the output is flushed so that it is shown in order
+    /// with the log of the interpreter.
+    pub fn write(&mut self, text: &str) -> Result<(), Error> {
+        let r = match self.output.write_all(text.as_bytes()) {
+            Ok(_) => self.output.flush(),
+            Err(e) => Err(e),
+        };
+        match r {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                eprintln!("[console] [write] write output failed, error: {}", e);
+                Err(Error::IoFailed)
+            }
+        }
+    }
+
+    /// fill reads the next line of the input when the current line is used up.
+    fn fill(&mut self) -> Result<&mut VecDeque<char>, Error> {
+        if !matches!(&self.line, Some(line) if !line.is_empty()) {
+            let mut text = String::new();
+            match self.input.read_line(&mut text) {
+                Ok(0) => {
+                    eprintln!("[console] [fill] read past the end of the input");
+                    return Err(Error::IoFailed);
+                }
+                Ok(_) => self.line = Some(text.chars().collect()),
+                Err(e) => {
+                    eprintln!("[console] [fill] read input failed, error: {}", e);
+                    return Err(Error::IoFailed);
+                }
+            };
+        }
+
+        match self.line.as_mut() {
+            Some(line) => Ok(line),
+            None => Err(Error::IoFailed),
+        }
+    }
+
+    /// read_char reads the next character, the line break is read as '\n'.
+    fn read_char(&mut self) -> Result<char, Error> {
+        match self.fill() {
+            Ok(line) => line.pop_front().ok_or(Error::IoFailed),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// read_token skips the blanks and the line breaks and reads the characters up to the next
+    /// blank, which is the text of a number.
+    fn read_token(&mut self) -> Result<String, Error> {
+        let line = loop {
+            let line = match self.fill() {
+                Ok(line) => line,
+                Err(e) => return Err(e),
+            };
+            while line.front().is_some_and(|c| c.is_whitespace()) {
+                line.pop_front();
+            }
+            if !line.is_empty() {
+                break line;
+            }
+        };
+
+        let mut token = String::new();
+        while let Some(c) = line.front().copied().filter(|c| !c.is_whitespace()) {
+            token.push(c);
+            line.pop_front();
+        }
+        Ok(token)
+    }
+
+    /// read_string reads the rest of the line, the line break is left to ReadLn.
+    fn read_string(&mut self) -> Result<String, Error> {
+        let line = match self.fill() {
+            Ok(line) => line,
+            Err(e) => return Err(e),
+        };
+
+        let mut text = String::new();
+        while let Some(c) = line.front().copied().filter(|c| *c != '\n' && *c != '\r') {
+            text.push(c);
+            line.pop_front();
+        }
+        Ok(text)
+    }
+
+    /// skip_line discards the rest of the line being read, or the next line when no line is
+    /// being read, so the next value is read from the following line.
+    pub fn skip_line(&mut self) -> Result<(), Error> {
+        if self.line.is_none() {
+            if let Err(e) = self.fill() {
+                return Err(e);
+            }
+        }
+        self.line = None;
+        Ok(())
+    }
+
+    /// read reads a value of the type from the input, a number is read from the next word and a
+    /// string from the rest of the line.
+    pub fn read(&mut self, r#type: &DataType) -> Result<Value, Error> {
+        let number_type = match r#type {
+            DataType::Simple(t) => *t,
+            DataType::ShortString(_) => NumberType::String,
+            DataType::Subrange(st) => return self.read(&st.host()),
+            t => {
+                eprintln!("[console] [read] a variable of type {} can not be read", t);
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        let text = match number_type {
+            NumberType::Integer => match self.read_token() {
+                Ok(token) => match token.parse::<i32>() {
+                    Ok(n) => n.to_string(),
+                    Err(_) => {
+                        eprintln!("[console] [read] '{}' is not an INTEGER", token);
+                        return Err(Error::InvalidInput);
+                    }
+                },
+                Err(e) => return Err(e),
+            },
+            NumberType::Real => match self.read_token() {
+                Ok(token) => match token.parse::<f32>() {
+                    Ok(n) => n.to_string(),
+                    Err(_) => {
+                        eprintln!("[console] [read] '{}' is not a REAL", token);
+                        return Err(Error::InvalidInput);
+                    }
+                },
+                Err(e) => return Err(e),
+            },
+            NumberType::Char => match self.read_char() {
+                Ok(c) => c.to_string(),
+                Err(e) => return Err(e),
+            },
+            NumberType::String => match self.read_string() {
+                Ok(s) => s,
+                Err(e) => return Err(e),
+            },
+            NumberType::Boolean => {
+                eprintln!(
+                    "[console] [read] a variable of type {} can not be read",
+                    r#type
+                );
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        Ok(Value::new(number_type.r#type(), &text))
+    }
+}
+
+/// format returns the text of the value written by Write, right-aligned in a field of `width`
+/// characters. A REAL is written in floating-point notation, e.g. ` 3.1400000000E+00`, unless the
+/// number of `decimals` is given.
+pub fn format(value: &Value, width: Option<i32>, decimals: Option<i32>) -> Result<String, Error> {
+    let r#type = match DataType::from_tag(value.r#type()) {
+        Some(t) => t,
+        None => {
+            eprintln!(
+                "[console] [format] value of type {} can not be written",
+                value.r#type()
+            );
+            return Err(Error::TypeMismatch);
+        }
+    };
+
+    let text = match (&r#type, decimals) {
+        (DataType::Simple(NumberType::Real), _) => {
+            let x = match value.value().parse::<f64>() {
+                Ok(x) => x,
+                Err(e) => {
+                    eprintln!(
+                        "[console] [format] parse REAL '{}' failed, error: {}",
+                        value.value(),
+                        e
+                    );
+                    return Err(Error::InvalidSyntax);
+                }
+            };
+            match decimals {
+                Some(d) => format!("{:.*}", d.max(0) as usize, x),
+                None => {
+                    let digits = match width {
+                        Some(w) => (w.max(0) as usize).saturating_sub(REAL_OVERHEAD).max(1),
+                        None => REAL_DIGITS,
+                    };
+                    scientific(x, digits)
+                }
+            }
+        }
+        (_, Some(_)) => {
+            eprintln!(
+                "[console] [format] decimals can not be given for a value of type {}",
+                r#type
+            );
+            return Err(Error::TypeMismatch);
+        }
+        (DataType::Simple(NumberType::Boolean), _) => value.value().to_uppercase(),
+        (DataType::Simple(_), _) | (DataType::Enum(_), _) => value.value().to_string(),
+        (t, _) => {
+            eprintln!("[console] [format] value of type {} can not be written", t);
+            return Err(Error::TypeMismatch);
+        }
+    };
+
+    Ok(format!("{:>1$}", text, width.unwrap_or(0).max(0) as usize))
+}
+
+/// scientific writes the number with one digit before the decimal point and a signed exponent of
+/// at least two digits, a blank stands for the sign of a positive number.
+fn scientific(x: f64, digits: usize) -> String {
+    let text = format!("{:.*E}", digits, x.abs());
+    let (mantissa, exponent) = match text.split_once('E') {
+        Some((m, e)) => (m, e.parse::<i32>().unwrap_or(0)),
+        None => (text.as_str(), 0),
+    };
+    format!(
+        "{}{}E{}{:02}",
+        if x < 0.0 { '-' } else { ' ' },
+        mantissa,
+        if exponent < 0 { '-' } else { '+' },
+        exponent.abs()
+    )
+}
@@ -0,0 +1,472 @@
+use crate::ast::Value;
+use crate::error::Error;
+use crate::lexer::lexeme::{number::NumberType, Type};
+use itertools::Itertools;
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::rc::Rc;
+
+/// DataType is the type of a variable once its type spec is resolved through the symbol table.
+#[derive(Clone, PartialEq)]
+pub enum DataType {
+    Simple(NumberType),
+    ShortString(usize), // STRING[n], a string of at most n characters.
+    Enum(Rc<EnumType>),
+    Subrange(Rc<SubrangeType>),
+    Set(Rc<SetType>),
+    Pointer(Rc<PointerType>),
+    Array(Rc<ArrayType>),
+    Record(Rc<RecordType>),
+}
+
+impl DataType {
+    /// from_tag returns the simple or enumerated type named by the type of a value, values carry
+    /// the name of their type and an enumerated type is named by its constants, e.g. (RED, BLUE).
+    pub fn from_tag(tag: &str) -> Option<DataType> {
+        if let Ok(t) = NumberType::to_number_type(tag) {
+            return Some(DataType::Simple(t));
+        }
+
+        if let Some(base) = tag.strip_prefix("SET OF ") {
+            return DataType::from_tag(base).map(|t| DataType::Set(Rc::new(SetType::new(t))));
+        }
+
+        let names = tag.strip_prefix('(')?.strip_suffix(')')?;
+        Some(DataType::Enum(Rc::new(EnumType::new(
+            names.split(", ").map(|n| n.to_string()).collect(),
+        ))))
+    }
+
+    /// tag returns the name of the type carried by the values of this type, the values of a
+    /// subrange are values of its host type.
+    pub fn tag(&self) -> String {
+        match self {
+            DataType::Simple(t) => t.r#type().to_string(),
+            DataType::ShortString(_) => NumberType::String.r#type().to_string(),
+            DataType::Subrange(st) => st.host.tag(),
+            DataType::Set(st) => format!("SET OF {}", st.base.tag()),
+            _ => self.to_string(),
+        }
+    }
+
+    /// bounds returns the smallest and the largest ordinal numbers of an ordinal type.
+    pub fn bounds(&self) -> Option<(i32, i32)> {
+        match self {
+            DataType::Simple(NumberType::Integer) => Some((i32::MIN, i32::MAX)),
+            DataType::Simple(NumberType::Boolean) => Some((0, 1)),
+            DataType::Simple(NumberType::Char) => Some((0, 255)),
+            DataType::Enum(et) => Some((0, et.names.len() as i32 - 1)),
+            DataType::Subrange(st) => Some((st.low, st.high)),
+            _ => None,
+        }
+    }
+
+    pub fn is_ordinal(&self) -> bool {
+        self.bounds().is_some()
+    }
+
+    /// value_at builds the value of an ordinal type from its ordinal number, the number must
+    /// be in the range of a subrange type.
+    pub fn value_at(&self, ordinal: i32) -> Result<Value, Error> {
+        match self {
+            DataType::Simple(t) => Value::from_ordinal(*t, ordinal),
+            DataType::Enum(et) => match et.names.get(ordinal as usize) {
+                Some(name) if ordinal >= 0 => Ok(Value::new(&self.tag(), name)),
+                _ => {
+                    eprintln!(
+                        "[data type] [value_at] {} is out of the range of {}",
+                        ordinal, self
+                    );
+                    Err(Error::OutOfRange)
+                }
+            },
+            DataType::Subrange(st) => {
+                if ordinal < st.low || ordinal > st.high {
+                    eprintln!(
+                        "[data type] [value_at] {} is out of the range of {}",
+                        ordinal, self
+                    );
+                    return Err(Error::OutOfRange);
+                }
+                st.host.value_at(ordinal)
+            }
+            _ => {
+                eprintln!("[data type] [value_at] {} is not an ordinal type", self);
+                Err(Error::TypeMismatch)
+            }
+        }
+    }
+
+    /// initial_value returns the value of a newly declared variable, a variable of a simple type
+    /// is undefined until it is assigned, the elements of a structured variable are zeroed.
+    pub fn initial_value(&self) -> Option<Value> {
+        match self {
+            DataType::Simple(_)
+            | DataType::ShortString(_)
+            | DataType::Enum(_)
+            | DataType::Subrange(_)
+            | DataType::Set(_)
+            | DataType::Pointer(_) => None,
+            _ => Some(self.zero_value()),
+        }
+    }
+
+    /// component returns the position of the component chosen by the selection in the items of
+    /// a structured value, and the type of the component.
+    fn component(&self, selection: &Selection) -> Result<(usize, DataType), Error> {
+        match (self, selection) {
+            (DataType::Array(at), Selection::Index(index)) => match at.offset(index) {
+                Ok(offset) => Ok((offset, at.element.clone())),
+                Err(e) => Err(e),
+            },
+            (DataType::Record(rt), Selection::Field(name)) => match rt.field(name) {
+                Some(f) => Ok(f),
+                None => {
+                    eprintln!(
+                        "[data type] [component] field '{}' not found in {}",
+                        name, self
+                    );
+                    Err(Error::FieldNotFound)
+                }
+            },
+            _ => {
+                eprintln!(
+                    "[data type] [component] {} can not be selected from {}",
+                    selection, self
+                );
+                Err(Error::TypeMismatch)
+            }
+        }
+    }
+
+    /// select returns the type and the value of the component chosen by the selection.
+    pub fn select(&self, value: &Value, selection: &Selection) -> Result<(DataType, Value), Error> {
+        match self.component(selection) {
+            Ok((offset, r#type)) => Ok((r#type, value.items()[offset].clone())),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// replace returns the value with the component chosen by the selections replaced, the
+    /// component is converted to its type the same way as for an assignment.
+    pub fn replace(
+        &self,
+        value: Option<&Value>,
+        selections: &[Selection],
+        component: Value,
+    ) -> Result<Value, Error> {
+        let (selection, rest) = match selections.split_first() {
+            Some(s) => s,
+            None => return component.cast(self),
+        };
+
+        let value = match value {
+            Some(v) => v,
+            None => {
+                eprintln!(
+                    "[data type] [replace] {} can not be selected from {}",
+                    selection, self
+                );
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        let (offset, r#type) = match self.component(selection) {
+            Ok(c) => c,
+            Err(e) => return Err(e),
+        };
+
+        let mut items: Vec<Value> = value.items().iter().map(|v| v.clone()).collect();
+        items[offset] = match r#type.replace(Some(&items[offset]), rest, component) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Value::structured(value.r#type(), items))
+    }
+
+    fn zero_value(&self) -> Value {
+        match self {
+            DataType::Simple(t) => match t {
+                NumberType::Integer | NumberType::Real => Value::new(t.r#type(), "0"),
+                NumberType::Boolean => Value::new(t.r#type(), "false"),
+                NumberType::Char => Value::new(t.r#type(), "\0"),
+                NumberType::String => Value::new(t.r#type(), ""),
+            },
+            DataType::ShortString(_) => Value::new(NumberType::String.r#type(), ""),
+            DataType::Enum(_) | DataType::Subrange(_) => match self.bounds() {
+                Some((low, _)) => self.value_at(low).unwrap_or(Value::new(&self.tag(), "")),
+                None => Value::new(&self.tag(), ""),
+            },
+            DataType::Set(_) => Value::set(&self.tag(), Vec::new()),
+            DataType::Pointer(_) => Value::new(&self.tag(), NIL),
+            DataType::Array(at) => Value::structured(
+                &self.to_string(),
+                (at.low..=at.high)
+                    .map(|_| at.element.zero_value())
+                    .collect(),
+            ),
+            DataType::Record(rt) => Value::structured(
+                &self.to_string(),
+                rt.fields.iter().map(|(_, t)| t.zero_value()).collect(),
+            ),
+        }
+    }
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataType::Simple(t) => write!(f, "{}", t.r#type()),
+            DataType::ShortString(n) => write!(f, "STRING[{}]", n),
+            DataType::Enum(et) => write!(f, "({})", et.names.join(", ")),
+            DataType::Subrange(st) => write!(f, "{}", st),
+            DataType::Set(st) => write!(f, "SET OF {}", st.base),
+            DataType::Pointer(pt) => write!(f, "^{}", pt.target),
+            DataType::Array(at) => write!(f, "{}", at),
+            DataType::Record(rt) => write!(f, "{}", rt),
+        }
+    }
+}
+
+/// EnumType is `(name, ...)`, the ordinal number of a constant is its position in the list.
+#[derive(PartialEq)]
+pub struct EnumType {
+    names: Vec<String>,
+}
+
+impl EnumType {
+    pub fn new(names: Vec<String>) -> EnumType {
+        EnumType { names }
+    }
+
+    /// position returns the ordinal number of the constant, names are case-insensitive.
+    pub fn position(&self, name: &str) -> Option<i32> {
+        self.names
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(name))
+            .map(|i| i as i32)
+    }
+}
+
+/// SubrangeType is `low..high`, a range of the values of the ordinal host type.
+#[derive(PartialEq)]
+pub struct SubrangeType {
+    host: DataType,
+    low: i32,
+    high: i32,
+}
+
+impl SubrangeType {
+    pub fn new(host: DataType, low: i32, high: i32) -> SubrangeType {
+        SubrangeType { host, low, high }
+    }
+
+    pub fn host(&self) -> DataType {
+        self.host.clone()
+    }
+}
+
+impl Display for SubrangeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bound = |ordinal: i32| match self.host.value_at(ordinal) {
+            Ok(v) => v.value().to_string(),
+            Err(_) => ordinal.to_string(),
+        };
+        write!(f, "{}..{}", bound(self.low), bound(self.high))
+    }
+}
+
+/// EMPTY_SET is the type of the empty set `[]`, it is compatible with the sets of every base type.
+pub const EMPTY_SET: &str = "SET";
+
+/// SetType is `SET OF base`, the base type is an ordinal type whose ordinal numbers are in 0..255.
+/// The items of a set value are its elements ordered by their ordinal numbers.
+#[derive(PartialEq)]
+pub struct SetType {
+    base: DataType,
+}
+
+impl SetType {
+    pub fn new(base: DataType) -> SetType {
+        SetType { base }
+    }
+
+    pub fn base(&self) -> DataType {
+        self.base.clone()
+    }
+
+    /// value builds the set of the elements with the ordinal numbers, which must be in the range
+    /// of the base type.
+    pub fn value(&self, ordinals: &BTreeSet<i32>) -> Result<Value, Error> {
+        let mut items = Vec::new();
+        for n in ordinals.iter() {
+            match self.base.value_at(*n) {
+                Ok(v) => items.push(v),
+                Err(e) => return Err(e),
+            };
+        }
+        Ok(Value::set(&format!("SET OF {}", self.base.tag()), items))
+    }
+}
+
+/// POINTER is the type of the untyped pointers, NIL and the addresses taken by `@`, they can be
+/// assigned to and compared with the pointers of every type.
+pub const POINTER: &str = "POINTER";
+
+/// NIL is the value of a pointer which points to no variable.
+pub const NIL: &str = "NIL";
+
+/// PointerType is `^target`, the target is the name of a type which may be declared after the
+/// pointer type, so it is looked up when a variable is allocated. The value of a pointer is the
+/// address of its variable in the heap.
+#[derive(PartialEq)]
+pub struct PointerType {
+    target: String,
+}
+
+impl PointerType {
+    pub fn new(target: &str) -> PointerType {
+        PointerType {
+            target: target.to_uppercase(),
+        }
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+}
+
+/// ArrayType is `ARRAY[index] OF element`, the index type is an ordinal type whose bounds are the
+/// bounds of the array. An array with several indexes is an array of arrays, so
+/// `ARRAY[1..2, 1..3] OF INTEGER` is the same type as `ARRAY[1..2] OF ARRAY[1..3] OF INTEGER`.
+#[derive(PartialEq)]
+pub struct ArrayType {
+    index: DataType,
+    low: i32,
+    high: i32,
+    element: DataType,
+}
+
+impl ArrayType {
+    /// new returns the array type indexed by the ordinal type, INTEGER has too many values to be
+    /// an index type.
+    pub fn new(index: DataType, element: DataType) -> Result<ArrayType, Error> {
+        let (low, high) = match index.bounds() {
+            Some(b) if index != DataType::Simple(NumberType::Integer) => b,
+            _ => {
+                eprintln!("[array] [new] {} can not be used as an index type", index);
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        Ok(ArrayType {
+            index,
+            low,
+            high,
+            element,
+        })
+    }
+
+    pub fn index(&self) -> DataType {
+        self.index.clone()
+    }
+
+    /// offset returns the position of the element selected by the index in the items of the array.
+    pub fn offset(&self, index: &Value) -> Result<usize, Error> {
+        if self.index.tag() != index.r#type() {
+            eprintln!(
+                "[array] [offset] index of type {} can not be used for {}",
+                index.r#type(),
+                self
+            );
+            return Err(Error::TypeMismatch);
+        }
+
+        let ordinal = match index.ordinal() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        if ordinal < self.low || ordinal > self.high {
+            eprintln!(
+                "[array] [offset] index {} is out of the range of {}",
+                index.value(),
+                self
+            );
+            return Err(Error::IndexOutOfRange);
+        }
+
+        Ok((ordinal - self.low) as usize)
+    }
+}
+
+impl Display for ArrayType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ARRAY[{}] OF {}", self.index, self.element)
+    }
+}
+
+/// RecordType is `RECORD field: type; ... END`, the items of a record value are the values of
+/// its fields in the declared order.
+#[derive(PartialEq)]
+pub struct RecordType {
+    fields: Vec<(String, DataType)>,
+}
+
+impl RecordType {
+    pub fn new(fields: Vec<(String, DataType)>) -> RecordType {
+        RecordType { fields }
+    }
+
+    pub fn fields(&self) -> &[(String, DataType)] {
+        &self.fields
+    }
+
+    /// field returns the position and the type of the field, field names are case-insensitive.
+    pub fn field(&self, name: &str) -> Option<(usize, DataType)> {
+        self.fields
+            .iter()
+            .position(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|i| (i, self.fields[i].1.clone()))
+    }
+}
+
+impl Display for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, r#type)| format!("{}: {}; ", name.to_uppercase(), r#type))
+            .join("");
+        write!(f, "RECORD {}END", fields)
+    }
+}
+
+/// Selection is a selector of a designator once it is evaluated, either the index of an array
+/// element, the name of a record field or the dereference of a pointer.
+pub enum Selection {
+    Index(Value),
+    Field(String),
+    Deref,
+}
+
+impl Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Selection::Index(v) => write!(f, "[{}]", v.content()),
+            Selection::Field(name) => write!(f, ".{}", name),
+            Selection::Deref => write!(f, "^"),
+        }
+    }
+}
+
+impl Clone for Selection {
+    fn clone(&self) -> Self {
+        match self {
+            Selection::Index(v) => Selection::Index(v.clone()),
+            Selection::Field(name) => Selection::Field(name.clone()),
+            Selection::Deref => Selection::Deref,
+        }
+    }
+}
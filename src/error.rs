@@ -9,6 +9,28 @@ pub enum Error {
     FileNotFound,
     VarRedefined,
     ProcedureNotFound,
+    TypeMismatch,
+    ControlVariableAssigned,
+    CaseLabelNotFound,
+    DivisionByZero,
+    ResultNotAssigned,
+    ConstantAssigned,
+    VariableRequired,
+    TypeNotFound,
+    IndexOutOfRange,
+    FieldNotFound,
+    OutOfRange,
+    NilPointer,
+    InvalidPointer,
+    ForwardMismatch,
+    IoFailed,
+    InvalidInput,
+    OutOfDomain,
+    LabelNotFound,
+    LabelRedefined,
+    InvalidGoto,
+    LoopRequired,
+    ArgumentCountMismatch,
 }
 
 impl error::Error for Error {}
@@ -21,6 +43,30 @@ impl fmt::Display for Error {
             Error::FileNotFound => write!(f, "file not found"),
             Error::VarRedefined => write!(f, "variable was redefined"),
             Error::ProcedureNotFound => write!(f, "procedure not found"),
+            Error::TypeMismatch => write!(f, "type mismatch"),
+            Error::ControlVariableAssigned => write!(f, "control variable was assigned"),
+            Error::CaseLabelNotFound => write!(f, "case label not found"),
+            Error::DivisionByZero => write!(f, "division by zero"),
+            Error::ResultNotAssigned => write!(f, "function result was not assigned"),
+            Error::ConstantAssigned => write!(f, "constant can not be assigned"),
+            Error::VariableRequired => write!(f, "variable identifier expected"),
+            Error::TypeNotFound => write!(f, "type not found"),
+            Error::IndexOutOfRange => write!(f, "index out of range"),
+            Error::FieldNotFound => write!(f, "field not found"),
+            Error::OutOfRange => write!(f, "value out of range"),
+            Error::NilPointer => write!(f, "nil pointer dereference"),
+            Error::InvalidPointer => write!(f, "invalid pointer"),
+            Error::ForwardMismatch => {
+                write!(f, "declaration does not match the forward declaration")
+            }
+            Error::IoFailed => write!(f, "input/output failed"),
+            Error::InvalidInput => write!(f, "invalid input"),
+            Error::OutOfDomain => write!(f, "argument out of the domain of the function"),
+            Error::LabelNotFound => write!(f, "label not found"),
+            Error::LabelRedefined => write!(f, "label was redefined"),
+            Error::InvalidGoto => write!(f, "goto into a structured statement"),
+            Error::LoopRequired => write!(f, "statement is only allowed inside a loop"),
+            Error::ArgumentCountMismatch => write!(f, "wrong number of arguments"),
         }
     }
 }
@@ -33,6 +79,28 @@ impl Error {
             Error::FileNotFound => "file not found",
             Error::VarRedefined => "variable was redefined",
             Error::ProcedureNotFound => "procedure not found",
+            Error::TypeMismatch => "type mismatch",
+            Error::ControlVariableAssigned => "control variable was assigned",
+            Error::CaseLabelNotFound => "case label not found",
+            Error::DivisionByZero => "division by zero",
+            Error::ResultNotAssigned => "function result was not assigned",
+            Error::ConstantAssigned => "constant can not be assigned",
+            Error::VariableRequired => "variable identifier expected",
+            Error::TypeNotFound => "type not found",
+            Error::IndexOutOfRange => "index out of range",
+            Error::FieldNotFound => "field not found",
+            Error::OutOfRange => "value out of range",
+            Error::NilPointer => "nil pointer dereference",
+            Error::InvalidPointer => "invalid pointer",
+            Error::ForwardMismatch => "declaration does not match the forward declaration",
+            Error::IoFailed => "input/output failed",
+            Error::InvalidInput => "invalid input",
+            Error::OutOfDomain => "argument out of the domain of the function",
+            Error::LabelNotFound => "label not found",
+            Error::LabelRedefined => "label was redefined",
+            Error::InvalidGoto => "goto into a structured statement",
+            Error::LoopRequired => "statement is only allowed inside a loop",
+            Error::ArgumentCountMismatch => "wrong number of arguments",
         }
     }
 }
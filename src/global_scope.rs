@@ -1,14 +1,20 @@
-use crate::ast::procedure::Procedure;
+use crate::ast::{
+    procedure::{Procedure, RESULT, RETURN_VALUE},
+    Value,
+};
+use crate::builtin::Builtin;
+use crate::console::Console;
+use crate::data_type::{DataType, Selection, NIL};
 use crate::error::Error;
 use crate::lexer::lexeme::keyword::Keyword;
 use crate::lexer::lexeme::number::NumberType;
-use crate::lexer::lexeme::{Type, Value};
+use crate::lexer::lexeme::{Type, Value as _};
 use itertools::Itertools;
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::HashMap,
     fmt::{self, Display},
-    rc::Rc,
+    rc::{Rc, Weak},
 };
 
 /* KeywordSymbol */
@@ -43,63 +49,251 @@ impl Clone for KeywordSymbol {
 
 /* VariableSymbol */
 pub struct VariableSymbol {
-    r#type: NumberType,
-    value: Option<String>,
+    r#type: DataType,
+    value: Option<Value>,
 }
 
 impl VariableSymbol {
-    pub fn new(r#type: NumberType, value: Option<String>) -> VariableSymbol {
-        VariableSymbol {
-            r#type,
-            value: value.clone(),
-        }
+    pub fn new(r#type: DataType, value: Option<Value>) -> VariableSymbol {
+        VariableSymbol { r#type, value }
     }
 
-    pub fn r#type(&self) -> NumberType {
-        self.r#type
+    pub fn r#type(&self) -> DataType {
+        self.r#type.clone()
     }
 
-    pub fn value(&self) -> Option<String> {
-        self.value.clone()
+    pub fn value(&self) -> Option<Value> {
+        match &self.value {
+            Some(v) => Some(v.clone()),
+            None => None,
+        }
     }
 }
 
 impl Display for VariableSymbol {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let value = match &self.value {
-            Some(v) => v,
-            None => "none",
+            Some(v) => v.content(),
+            None => String::from("none"),
         };
-        write!(f, "{{type: {}, value: {}}}", self.r#type.r#type(), value)
+        write!(f, "{{type: {}, value: {}}}", self.r#type, value)
     }
 }
 
 impl Clone for VariableSymbol {
     fn clone(&self) -> Self {
         Self {
-            r#type: self.r#type,
+            r#type: self.r#type.clone(),
+            value: self.value(),
+        }
+    }
+}
+
+/* ConstantSymbol */
+pub struct ConstantSymbol {
+    r#type: DataType,
+    value: Value,
+}
+
+impl ConstantSymbol {
+    pub fn new(r#type: DataType, value: Value) -> ConstantSymbol {
+        ConstantSymbol { r#type, value }
+    }
+
+    pub fn r#type(&self) -> DataType {
+        self.r#type.clone()
+    }
+
+    pub fn value(&self) -> Value {
+        self.value.clone()
+    }
+}
+
+impl Display for ConstantSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{type: {}, value: {}}}",
+            self.r#type,
+            self.value.content()
+        )
+    }
+}
+
+impl Clone for ConstantSymbol {
+    fn clone(&self) -> Self {
+        Self {
+            r#type: self.r#type.clone(),
             value: self.value.clone(),
         }
     }
 }
 
+/* ReferenceSymbol */
+/// ReferenceSymbol refers to the variable `name` defined in `scope`, or to its component chosen
+/// by the selections. It is used by the VAR and OUT parameters, so that reading and writing the
+/// parameter reads and writes the caller's variable.
+pub struct ReferenceSymbol {
+    scope: Rc<RefCell<Scope>>,
+    name: String,
+    selections: Vec<Selection>,
+}
+
+impl ReferenceSymbol {
+    pub fn new(
+        scope: Rc<RefCell<Scope>>,
+        name: &str,
+        selections: Vec<Selection>,
+    ) -> ReferenceSymbol {
+        ReferenceSymbol {
+            scope,
+            name: name.to_string(),
+            selections,
+        }
+    }
+
+    /// get returns the variable referred to, a component is returned as a variable of its type.
+    pub fn get(&self) -> Option<Identifier> {
+        let vs = match self.scope.borrow().get(&self.name) {
+            Some(Identifier::Variable(vs)) => vs,
+            id => return id,
+        };
+
+        if vs.value().is_none() {
+            return Some(Identifier::Variable(vs));
+        }
+
+        let heap = self.scope.borrow().heap();
+        match Heap::component(&heap, vs, &self.selections) {
+            Ok(vs) => Some(Identifier::Variable(vs)),
+            Err(_) => None,
+        }
+    }
+
+    /// set writes the variable referred to, a component is written into the caller's variable or
+    /// into the heap variable a pointer of the caller points to.
+    pub fn set(&self, id: Identifier) -> Result<(), Error> {
+        if self.selections.is_empty() {
+            return self.scope.borrow_mut().set(&self.name, id);
+        }
+
+        let component = match id {
+            Identifier::Variable(vs) => vs.value(),
+            _ => None,
+        };
+        let component = match component {
+            Some(v) => v,
+            None => {
+                eprintln!("[set] component of '{}' can not be undefined", self.name);
+                return Err(Error::InvalidSyntax);
+            }
+        };
+
+        let vs = match self.scope.borrow().get(&self.name) {
+            Some(Identifier::Variable(vs)) => vs,
+            _ => return Err(Error::VarNotFound),
+        };
+
+        let heap = self.scope.borrow().heap();
+        let val = match Heap::store(&heap, &vs, &self.selections, component) {
+            Ok(Some(v)) => v,
+            Ok(None) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        self.scope.borrow_mut().set(
+            &self.name,
+            Identifier::Variable(VariableSymbol::new(vs.r#type(), Some(val))),
+        )
+    }
+}
+
+impl Display for ReferenceSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{scope: {}, name: {}{}}}",
+            self.scope.borrow().name,
+            self.name,
+            self.selections.iter().join("")
+        )
+    }
+}
+
+impl Clone for ReferenceSymbol {
+    fn clone(&self) -> Self {
+        Self {
+            scope: self.scope.clone(),
+            name: self.name.clone(),
+            selections: self.selections.clone(),
+        }
+    }
+}
+
+/* TypeSymbol */
+pub struct TypeSymbol {
+    name: String,
+    r#type: DataType,
+}
+
+impl TypeSymbol {
+    pub fn new(name: &str, r#type: DataType) -> TypeSymbol {
+        TypeSymbol {
+            name: name.to_string(),
+            r#type,
+        }
+    }
+
+    pub fn r#type(&self) -> DataType {
+        self.r#type.clone()
+    }
+}
+
+impl Display for TypeSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{name: {}, type: {}}}", self.name, self.r#type)
+    }
+}
+
+impl Clone for TypeSymbol {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            r#type: self.r#type.clone(),
+        }
+    }
+}
+
 /* ProcedureSymbol */
+/// ProcedureSymbol is a procedure or a function with the scope declaring it, which is the parent
+/// of the scopes of its calls (the static link), so non-local names are resolved where the
+/// procedure is declared rather than where it is called.
 pub struct ProcedureSymbol {
     name: String,
     procedure: Rc<Procedure>,
+    scope: Weak<RefCell<Scope>>, // The symbol is held by this scope, so it does not own it.
 }
 
 impl ProcedureSymbol {
-    pub fn new(name: &str, procedure: Rc<Procedure>) -> ProcedureSymbol {
+    pub fn new(
+        name: &str,
+        procedure: Rc<Procedure>,
+        scope: &Rc<RefCell<Scope>>,
+    ) -> ProcedureSymbol {
         ProcedureSymbol {
             name: name.to_string(),
             procedure,
+            scope: Rc::downgrade(scope),
         }
     }
 
     pub fn procedure(&self) -> Rc<Procedure> {
         self.procedure.clone()
     }
+
+    pub fn scope(&self) -> Option<Rc<RefCell<Scope>>> {
+        self.scope.upgrade()
+    }
 }
 
 impl Display for ProcedureSymbol {
@@ -113,6 +307,199 @@ impl Clone for ProcedureSymbol {
         Self {
             name: self.name.clone(),
             procedure: self.procedure.clone(),
+            scope: self.scope.clone(),
+        }
+    }
+}
+
+/* Heap */
+/// Heap holds the variables allocated by New, a pointer points to the variable at its address.
+/// The address of a variable taken by `@` holds a reference to that variable.
+pub struct Heap {
+    cells: HashMap<usize, Identifier>,
+    next: usize,
+}
+
+impl Heap {
+    pub fn new() -> Heap {
+        Heap {
+            cells: HashMap::new(),
+            next: 1,
+        }
+    }
+
+    /// allocate stores the variable or the reference at a new address and returns the address.
+    pub fn allocate(&mut self, id: Identifier) -> usize {
+        let address = self.next;
+        self.next += 1;
+        self.cells.insert(address, id);
+        address
+    }
+
+    /// dispose releases the variable allocated by New at the address.
+    pub fn dispose(&mut self, address: usize) -> Result<(), Error> {
+        match self.cells.get(&address) {
+            Some(Identifier::Variable(_)) => {
+                self.cells.remove(&address);
+                Ok(())
+            }
+            _ => {
+                eprintln!("[heap] [dispose] no variable is allocated at {}", address);
+                Err(Error::InvalidPointer)
+            }
+        }
+    }
+
+    fn cell(&self, address: usize) -> Result<Identifier, Error> {
+        match self.cells.get(&address) {
+            Some(id) => Ok(id.clone()),
+            None => {
+                eprintln!("[heap] [cell] no variable is allocated at {}", address);
+                Err(Error::InvalidPointer)
+            }
+        }
+    }
+
+    /// dereference returns the address held by the pointer and the variable at the address.
+    pub fn dereference(
+        heap: &Rc<RefCell<Heap>>,
+        pointer: &VariableSymbol,
+    ) -> Result<(usize, VariableSymbol), Error> {
+        let value = match (pointer.r#type(), pointer.value()) {
+            (DataType::Pointer(_), Some(v)) => v,
+            (DataType::Pointer(_), None) => {
+                eprintln!("[heap] [dereference] pointer is undefined");
+                return Err(Error::InvalidPointer);
+            }
+            (t, _) => {
+                eprintln!("[heap] [dereference] {} is not a pointer type", t);
+                return Err(Error::TypeMismatch);
+            }
+        };
+
+        if value.value() == NIL {
+            eprintln!("[heap] [dereference] pointer is NIL");
+            return Err(Error::NilPointer);
+        }
+
+        let address = match value.value().parse::<usize>() {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!(
+                    "[heap] [dereference] parse address '{}' failed, error: {}",
+                    value.value(),
+                    e
+                );
+                return Err(Error::InvalidPointer);
+            }
+        };
+
+        /* The heap is not borrowed while the referenced variable is read */
+        let id = match heap.borrow().cell(address) {
+            Ok(id) => id,
+            Err(e) => return Err(e),
+        };
+        let id = match id {
+            Identifier::Reference(rs) => rs.get(),
+            id => Some(id),
+        };
+
+        match id {
+            Some(Identifier::Variable(vs)) => Ok((address, vs)),
+            _ => Err(Error::InvalidPointer),
+        }
+    }
+
+    /// component returns the component of the variable chosen by the selections as a variable
+    /// of its type, a dereference selects the variable the pointer points to.
+    pub fn component(
+        heap: &Rc<RefCell<Heap>>,
+        vs: VariableSymbol,
+        selections: &[Selection],
+    ) -> Result<VariableSymbol, Error> {
+        let mut vs = vs;
+        for selection in selections.iter() {
+            if let Selection::Deref = selection {
+                vs = match Heap::dereference(heap, &vs) {
+                    Ok((_, target)) => target,
+                    Err(e) => return Err(e),
+                };
+                continue;
+            }
+
+            let value = match vs.value() {
+                Some(v) => v,
+                None => {
+                    eprintln!(
+                        "[heap] [component] {} can not be selected from an undefined value",
+                        selection
+                    );
+                    return Err(Error::TypeMismatch);
+                }
+            };
+            vs = match vs.r#type().select(&value, selection) {
+                Ok((t, v)) => VariableSymbol::new(t, Some(v)),
+                Err(e) => return Err(e),
+            };
+        }
+        Ok(vs)
+    }
+
+    /// store writes the value to the component of the variable chosen by the selections. A
+    /// component reached through a pointer is written to the heap, otherwise the new value of the
+    /// variable is returned to be set in its scope.
+    pub fn store(
+        heap: &Rc<RefCell<Heap>>,
+        vs: &VariableSymbol,
+        selections: &[Selection],
+        value: Value,
+    ) -> Result<Option<Value>, Error> {
+        let deref = match selections
+            .iter()
+            .rposition(|s| matches!(s, Selection::Deref))
+        {
+            Some(i) => i,
+            None => {
+                return match vs.r#type().replace(vs.value().as_ref(), selections, value) {
+                    Ok(v) => Ok(Some(v)),
+                    Err(e) => Err(e),
+                };
+            }
+        };
+
+        /* The last dereference chooses the heap variable to write */
+        let pointer = match Heap::component(heap, vs.clone(), &selections[..deref]) {
+            Ok(p) => p,
+            Err(e) => return Err(e),
+        };
+        let (address, target) = match Heap::dereference(heap, &pointer) {
+            Ok(t) => t,
+            Err(e) => return Err(e),
+        };
+
+        let val =
+            match target
+                .r#type()
+                .replace(target.value().as_ref(), &selections[deref + 1..], value)
+            {
+                Ok(v) => v,
+                Err(e) => return Err(e),
+            };
+        let target = Identifier::Variable(VariableSymbol::new(target.r#type(), Some(val)));
+
+        let cell = match heap.borrow().cell(address) {
+            Ok(id) => id,
+            Err(e) => return Err(e),
+        };
+        match cell {
+            Identifier::Reference(rs) => match rs.set(target) {
+                Ok(_) => Ok(None),
+                Err(e) => Err(e),
+            },
+            _ => {
+                heap.borrow_mut().cells.insert(address, target);
+                Ok(None)
+            }
         }
     }
 }
@@ -120,7 +507,11 @@ impl Clone for ProcedureSymbol {
 pub enum Identifier {
     Keyword(KeywordSymbol),
     Variable(VariableSymbol),
+    Constant(ConstantSymbol),
+    Reference(ReferenceSymbol),
+    Type(TypeSymbol),
     Procedure(ProcedureSymbol),
+    Builtin(Builtin),
 }
 
 impl Identifier {
@@ -128,7 +519,11 @@ impl Identifier {
         match self {
             Identifier::Keyword(_) => "Keyword Symbol",
             Identifier::Variable(_) => "Variable Symbol",
+            Identifier::Constant(_) => "Constant Symbol",
+            Identifier::Reference(_) => "Reference Symbol",
+            Identifier::Type(_) => "Type Symbol",
             Identifier::Procedure(_) => "Procedure Symbol",
+            Identifier::Builtin(_) => "Builtin Symbol",
         }
     }
 }
@@ -142,9 +537,21 @@ impl Display for Identifier {
             Identifier::Variable(vs) => {
                 write!(f, "type: {}, value: {}", self.r#type(), vs)
             }
+            Identifier::Constant(cs) => {
+                write!(f, "type: {}, value: {}", self.r#type(), cs)
+            }
+            Identifier::Reference(rs) => {
+                write!(f, "type: {}, value: {}", self.r#type(), rs)
+            }
+            Identifier::Type(ts) => {
+                write!(f, "type: {}, value: {}", self.r#type(), ts)
+            }
             Identifier::Procedure(ps) => {
                 write!(f, "type: {}, value: {}", self.r#type(), ps)
             }
+            Identifier::Builtin(b) => {
+                write!(f, "type: {}, value: {}", self.r#type(), b)
+            }
         }
     }
 }
@@ -154,16 +561,36 @@ impl Clone for Identifier {
         match self {
             Identifier::Keyword(ks) => Identifier::Keyword(ks.clone()),
             Identifier::Variable(vs) => Identifier::Variable(vs.clone()),
+            Identifier::Constant(cs) => Identifier::Constant(cs.clone()),
+            Identifier::Reference(rs) => Identifier::Reference(rs.clone()),
+            Identifier::Type(ts) => Identifier::Type(ts.clone()),
             Identifier::Procedure(ps) => Identifier::Procedure(ps.clone()),
+            Identifier::Builtin(b) => Identifier::Builtin(*b),
         }
     }
 }
 
+/// Signal is a transfer of control pending in a scope. The statements being executed stop one
+/// after the other until the statement that handles the signal is reached.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Signal {
+    Goto(u32), // Continue with the statement with the label.
+    Break,     // Leave the innermost loop.
+    Continue,  // Start the next iteration of the innermost loop.
+    Exit,      // Leave the procedure or function, or the program.
+    Halt(i32), // Stop the program with the exit code.
+}
+
 pub struct Scope {
     name: String,
     symbol_table: HashMap<String, Identifier>,
     parent: Option<Rc<RefCell<Scope>>>,
     level: u32,
+    heap: Rc<RefCell<Heap>>, // The heap shared by all the scopes of the program.
+    console: Rc<RefCell<Console>>, // The standard input and output shared by all the scopes.
+    signal: Option<Signal>,
+    halted: Rc<Cell<Option<i32>>>, // The exit code once the program is halted, shared by all the scopes.
+    debug: bool, // Whether the scope is dumped to the standard error when it ends, inherited by the nested scopes.
 }
 
 impl Scope {
@@ -176,6 +603,29 @@ impl Scope {
             Keyword::Var,
             Keyword::Program,
             Keyword::Procedure,
+            Keyword::If,
+            Keyword::Then,
+            Keyword::Else,
+            Keyword::While,
+            Keyword::Do,
+            Keyword::Repeat,
+            Keyword::Until,
+            Keyword::For,
+            Keyword::To,
+            Keyword::Downto,
+            Keyword::Case,
+            Keyword::Of,
+            Keyword::Function,
+            Keyword::Const,
+            Keyword::Type,
+            Keyword::Array,
+            Keyword::Record,
+            Keyword::String,
+            Keyword::Set,
+            Keyword::Nil,
+            Keyword::Label,
+            Keyword::Goto,
+            Keyword::With,
         ] {
             symbol_table.insert(
                 kw.value().to_string(),
@@ -183,30 +633,105 @@ impl Scope {
             );
         }
 
+        /* The predefined types are visible from the outermost scope */
+        if parent.is_none() {
+            for t in [
+                NumberType::Integer,
+                NumberType::Real,
+                NumberType::Boolean,
+                NumberType::Char,
+            ] {
+                symbol_table.insert(
+                    t.r#type().to_string(),
+                    Identifier::Type(TypeSymbol::new(t.r#type(), DataType::Simple(t))),
+                );
+            }
+        }
+
+        let heap = match &parent {
+            Some(p) => p.borrow().heap(),
+            None => Rc::new(RefCell::new(Heap::new())),
+        };
+        let console = match &parent {
+            Some(p) => p.borrow().console(),
+            None => Rc::new(RefCell::new(Console::stdio())),
+        };
+        let halted = match &parent {
+            Some(p) => p.borrow().halted.clone(),
+            None => Rc::new(Cell::new(None)),
+        };
+
+        let debug = match &parent {
+            Some(p) => p.borrow().debug,
+            None => false,
+        };
+
         Scope {
             name: name.to_string(),
             symbol_table,
             parent,
             level,
+            heap,
+            console,
+            signal: None,
+            halted,
+            debug,
+        }
+    }
+
+    /// key returns the key of the identifier in the symbol table, identifiers are case-insensitive.
+    fn key(key: &str) -> String {
+        key.to_uppercase()
+    }
+
+    /// entry returns the key of `key` in this symbol table, RESULT names the return value of the
+    /// active function unless this scope declares it.
+    fn entry(&self, key: &str) -> String {
+        let key = Scope::key(key);
+        let return_value = Scope::key(RETURN_VALUE);
+        if key == Scope::key(RESULT)
+            && !self.symbol_table.contains_key(&key)
+            && self.symbol_table.contains_key(&return_value)
+        {
+            return return_value;
         }
+        key
     }
 
+    /// set assigns the identifier visible as `key`, a name not defined in this scope is assigned
+    /// in the enclosing scope defining it.
     pub fn set(&mut self, key: &str, id: Identifier) -> Result<(), Error> {
-        if let Some(_) = self.symbol_table.get(key) {
-            self.symbol_table.insert(key.to_string(), id);
-            Ok(())
-        } else {
-            println!("[set] variable '{}' is not defined in symbol table", key);
-            Err(Error::VarNotFound)
+        let entry = self.entry(key);
+        match self.symbol_table.get(&entry) {
+            /* Write through to the referenced variable */
+            Some(Identifier::Reference(rs)) => {
+                let rs = rs.clone();
+                rs.set(id)
+            }
+            Some(Identifier::Constant(_)) => {
+                eprintln!("[set] constant '{}' can not be assigned", key);
+                Err(Error::ConstantAssigned)
+            }
+            Some(_) => {
+                self.symbol_table.insert(entry, id);
+                Ok(())
+            }
+            None => match &self.parent {
+                Some(p) => p.borrow_mut().set(key, id),
+                None => {
+                    eprintln!("[set] variable '{}' is not defined in symbol table", key);
+                    Err(Error::VarNotFound)
+                }
+            },
         }
     }
 
     pub fn define(&mut self, key: &str, id: Identifier) -> Result<(), Error> {
-        if let Some(_) = self.symbol_table.get(key) {
+        if let Some(_) = self.symbol_table.get(&Scope::key(key)) {
             return Err(Error::VarRedefined);
         }
 
-        self.symbol_table.insert(key.to_string(), id);
+        self.symbol_table.insert(Scope::key(key), id);
 
         Ok(())
     }
@@ -216,18 +741,47 @@ impl Scope {
     }
 
     pub fn find_in_cur_scope(&self, key: &str) -> Option<Identifier> {
-        match self.symbol_table.get(key) {
+        match self.symbol_table.get(&self.entry(key)) {
             Some(id) => Some(id.clone()),
             None => None,
         }
     }
 
+    /// get returns the identifier visible as `key`, references are resolved to the variables they
+    /// refer to. A name not defined in any scope may be a builtin routine.
     pub fn get(&self, key: &str) -> Option<Identifier> {
         if let Some(id) = self.find_in_cur_scope(key) {
+            if let Identifier::Reference(rs) = id {
+                return rs.get();
+            }
             return Some(id);
         }
         match self.parent() {
             Some(s) => s.borrow().get(key),
+            None => Builtin::lookup(key).map(Identifier::Builtin),
+        }
+    }
+
+    /// defining_scope returns the scope whose symbol table defines `key` starting from `scope`.
+    pub fn defining_scope(scope: Rc<RefCell<Scope>>, key: &str) -> Option<Rc<RefCell<Scope>>> {
+        let entry = scope.borrow().entry(key);
+        if scope.borrow().symbol_table.contains_key(&entry) {
+            return Some(scope);
+        }
+        match scope.borrow().parent() {
+            Some(s) => Scope::defining_scope(s, key),
+            None => None,
+        }
+    }
+
+    /// lookup_scope returns the innermost scope named `name` starting from `scope`, which is
+    /// the scope of the active call of the procedure or function with that name.
+    pub fn lookup_scope(scope: Rc<RefCell<Scope>>, name: &str) -> Option<Rc<RefCell<Scope>>> {
+        if scope.borrow().name.eq_ignore_ascii_case(name) {
+            return Some(scope);
+        }
+        match scope.borrow().parent() {
+            Some(s) => Scope::lookup_scope(s, name),
             None => None,
         }
     }
@@ -236,30 +790,129 @@ impl Scope {
         self.level
     }
 
+    pub fn heap(&self) -> Rc<RefCell<Heap>> {
+        self.heap.clone()
+    }
+
+    pub fn console(&self) -> Rc<RefCell<Console>> {
+        self.console.clone()
+    }
+
+    /// signal returns the transfer of control pending in this scope, a halted program is halted
+    /// in every scope.
+    pub fn signal(&self) -> Option<Signal> {
+        match self.halted.get() {
+            Some(code) => Some(Signal::Halt(code)),
+            None => self.signal,
+        }
+    }
+
+    /// halted reports whether the program is halted, an expression waiting for the result of a
+    /// function that halted has no value and the statements leave without an error.
+    pub fn halted(&self) -> bool {
+        self.halted.get().is_some()
+    }
+
+    /// raise makes the transfer of control pending, the statements check it after each statement
+    /// they execute.
+    pub fn raise(&mut self, signal: Signal) {
+        match signal {
+            Signal::Halt(code) => self.halted.set(Some(code)),
+            _ => self.signal = Some(signal),
+        }
+    }
+
+    /// end_iteration handles the transfer of control pending after a statement of the body of a
+    /// loop, BREAK and CONTINUE are handled by the loop. It returns whether the loop ends.
+    pub fn end_iteration(&mut self) -> bool {
+        match self.signal() {
+            Some(Signal::Break) => {
+                self.signal = None;
+                true
+            }
+            Some(Signal::Continue) => {
+                self.signal = None;
+                false
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// clear_signal is called by the statement that handles the pending transfer of control.
+    pub fn clear_signal(&mut self) {
+        self.signal = None;
+    }
+
+    /// set_console replaces the standard input and output of the program, the scopes nested in
+    /// this scope afterwards share the new console.
+    #[cfg(test)]
+    pub fn set_console(&mut self, console: Console) {
+        self.console = Rc::new(RefCell::new(console));
+    }
+
+    pub fn debug(&self) -> bool {
+        self.debug
+    }
+
+    /// set_debug switches on or off the dump of the scopes, the scopes nested in this scope
+    /// afterwards inherit the switch.
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
+    /// print dumps the symbols of the scope to the standard error, the standard output is left to
+    /// the program.
     pub fn print(&self) {
-        println!("====================== SCOPE BEGIN ======================");
-        println!("SCOPE: {}, LEVEL: {}", self.name, self.level);
-        println!("KEYWORD:");
+        eprintln!("====================== SCOPE BEGIN ======================");
+        eprintln!("SCOPE: {}, LEVEL: {}", self.name, self.level);
+        eprintln!("KEYWORD:");
         for k in self.symbol_table.keys().sorted() {
             match self.symbol_table[k] {
-                Identifier::Keyword(_) => println!("key: {}, value: {}", k, self.symbol_table[k]),
+                Identifier::Keyword(_) => eprintln!("key: {}, value: {}", k, self.symbol_table[k]),
                 _ => continue,
             }
         }
-        println!("PROCEDURE:");
+        eprintln!("PROCEDURE:");
         for k in self.symbol_table.keys().sorted() {
             match self.symbol_table[k] {
-                Identifier::Procedure(_) => println!("key: {}, value: {}", k, self.symbol_table[k]),
+                Identifier::Procedure(_) => {
+                    eprintln!("key: {}, value: {}", k, self.symbol_table[k])
+                }
                 _ => continue,
             }
         }
-        println!("VARIABLE:");
+        eprintln!("TYPE:");
         for k in self.symbol_table.keys().sorted() {
             match self.symbol_table[k] {
-                Identifier::Variable(_) => println!("key: {}, value: {}", k, self.symbol_table[k]),
+                Identifier::Type(_) => eprintln!("key: {}, value: {}", k, self.symbol_table[k]),
                 _ => continue,
             }
         }
-        println!("======================  SCOPE END  ======================");
+        eprintln!("CONSTANT:");
+        for k in self.symbol_table.keys().sorted() {
+            match self.symbol_table[k] {
+                Identifier::Constant(_) => eprintln!("key: {}, value: {}", k, self.symbol_table[k]),
+                _ => continue,
+            }
+        }
+        eprintln!("VARIABLE:");
+        for k in self.symbol_table.keys().sorted() {
+            match self.symbol_table[k] {
+                Identifier::Variable(_) | Identifier::Reference(_) => {
+                    eprintln!("key: {}, value: {}", k, self.symbol_table[k])
+                }
+                _ => continue,
+            }
+        }
+        /* The heap is shared, so it is only printed with the scope of the program */
+        if self.level == 1 {
+            eprintln!("HEAP:");
+            let heap = self.heap.borrow();
+            for address in heap.cells.keys().sorted() {
+                eprintln!("address: {}, value: {}", address, heap.cells[address]);
+            }
+        }
+        eprintln!("======================  SCOPE END  ======================");
     }
 }
@@ -1,33 +1,51 @@
-use crate::{error::Error, global_scope::Scope, lexer::Lexer, parser::Parser};
+use crate::global_scope::{Scope, Signal};
+use crate::{error::Error, lexer::Lexer, parser::Parser};
 use std::{cell::RefCell, rc::Rc};
 
 pub struct Interpreter {
     pub parser: Parser,
+    debug: bool, // Whether the scopes are dumped to the standard error when they end.
 }
 
 impl Interpreter {
     pub fn new(code: &str) -> Result<Interpreter, Error> {
         let lexer = Lexer::new(code);
         let parser = Parser::new(lexer);
-        Ok(Interpreter { parser })
+        Ok(Interpreter {
+            parser,
+            debug: false,
+        })
     }
 
-    pub fn execute(&mut self) -> Result<(), Error> {
+    /// set_debug switches on or off the dump of the scopes.
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
+    /// execute runs the program and returns its exit code, which is given by HALT.
+    pub fn execute(&mut self) -> Result<i32, Error> {
         let root = match self.parser.parse() {
             Ok(n) => n,
             Err(e) => return Err(e),
         };
 
         let scope = Rc::new(RefCell::new(Scope::new("base", None, 0)));
-        match root.visit(scope) {
+        scope.borrow_mut().set_debug(self.debug);
+        match root.visit(scope.clone()) {
             Ok(info) => {
                 if let Some(v) = info.value() {
-                    println!("[interpreter] [execute] result: {}", v);
+                    eprintln!("[interpreter] [execute] result: {}", v);
                 }
             }
             Err(e) => return Err(e),
         };
 
-        Ok(())
+        /* HALT ends the program without an error, with its exit code */
+        if let Some(Signal::Halt(code)) = scope.borrow().signal() {
+            eprintln!("[interpreter] [execute] halted with exit code {}", code);
+            return Ok(code);
+        }
+
+        Ok(0)
     }
 }
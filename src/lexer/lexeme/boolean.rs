@@ -0,0 +1,30 @@
+use crate::lexer::lexeme::{number::NumberType, Type, Value};
+
+pub enum Boolean {
+    True,  // "TRUE"
+    False, // "FALSE"
+}
+
+impl Boolean {
+    pub fn bool(&self) -> bool {
+        match self {
+            Boolean::True => true,
+            Boolean::False => false,
+        }
+    }
+}
+
+impl Type for Boolean {
+    fn r#type(&self) -> &'static str {
+        NumberType::Boolean.r#type()
+    }
+}
+
+impl Value for Boolean {
+    fn value(&self) -> &'static str {
+        match self {
+            Boolean::True => "TRUE",
+            Boolean::False => "FALSE",
+        }
+    }
+}
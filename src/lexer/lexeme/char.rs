@@ -12,6 +12,12 @@ pub enum Char {
     Comma,      // ","
     LeftBrace,  // "{"
     RightBrace, // "}"
+    LeftBracket,  // "["
+    RightBracket, // "]"
+    Quote,        // "'"
+    Hash,         // "#"
+    Caret,        // "^"
+    At,           // "@"
 }
 
 impl Char {
@@ -28,6 +34,12 @@ impl Char {
             Char::Comma => ',',
             Char::LeftBrace => '{',
             Char::RightBrace => '}',
+            Char::LeftBracket => '[',
+            Char::RightBracket => ']',
+            Char::Quote => '\'',
+            Char::Hash => '#',
+            Char::Caret => '^',
+            Char::At => '@',
         }
     }
 }
@@ -46,6 +58,12 @@ impl Value for Char {
             Char::Comma => ",",
             Char::LeftBrace => "{",
             Char::RightBrace => "}",
+            Char::LeftBracket => "[",
+            Char::RightBracket => "]",
+            Char::Quote => "'",
+            Char::Hash => "#",
+            Char::Caret => "^",
+            Char::At => "@",
         }
     }
 }
@@ -64,6 +82,12 @@ impl Type for Char {
             Char::Comma => "comma",
             Char::LeftBrace => "left brace",
             Char::RightBrace => "right brace",
+            Char::LeftBracket => "left bracket",
+            Char::RightBracket => "right bracket",
+            Char::Quote => "quote",
+            Char::Hash => "hash",
+            Char::Caret => "caret",
+            Char::At => "at",
         }
     }
 }
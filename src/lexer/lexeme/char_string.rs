@@ -0,0 +1,36 @@
+use crate::lexer::lexeme::{number::NumberType, Type, Value};
+
+/// CharString is a quoted string or a character code, e.g. 'It''s' or #65, a character string
+/// of exactly one character is a CHAR.
+pub enum CharString {
+    Char(String),
+    String(String),
+}
+
+impl CharString {
+    pub fn new(s: String) -> CharString {
+        if s.chars().count() == 1 {
+            CharString::Char(s)
+        } else {
+            CharString::String(s)
+        }
+    }
+}
+
+impl Type for CharString {
+    fn r#type(&self) -> &'static str {
+        match self {
+            CharString::Char(_) => NumberType::Char.r#type(),
+            CharString::String(_) => NumberType::String.r#type(),
+        }
+    }
+}
+
+impl Value for CharString {
+    fn value(&self) -> &str {
+        match self {
+            CharString::Char(s) => s,
+            CharString::String(s) => s,
+        }
+    }
+}
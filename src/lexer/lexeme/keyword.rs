@@ -6,6 +6,35 @@ pub enum Keyword {
     Program,   // "PROGRAM"
     Var,       // "VAR"
     Procedure, // "PROCEDURE"
+    If,        // "IF"
+    Then,      // "THEN"
+    Else,      // "ELSE"
+    While,     // "WHILE"
+    Do,        // "DO"
+    Repeat,    // "REPEAT"
+    Until,     // "UNTIL"
+    For,       // "FOR"
+    To,        // "TO"
+    Downto,    // "DOWNTO"
+    Case,      // "CASE"
+    Of,        // "OF"
+    Function,  // "FUNCTION"
+    Const,     // "CONST"
+    Out,       // "OUT", not reserved, see Parser::is_word
+    Type,      // "TYPE"
+    Array,     // "ARRAY"
+    Record,    // "RECORD"
+    String,    // "STRING"
+    Set,       // "SET"
+    Nil,       // "NIL"
+    Forward,   // "FORWARD", not reserved, see Parser::is_word
+    Label,     // "LABEL"
+    Goto,      // "GOTO"
+    With,      // "WITH"
+    Break,     // "BREAK", not reserved, see Parser::is_word
+    Continue,  // "CONTINUE", not reserved, see Parser::is_word
+    Exit,      // "EXIT", not reserved, see Parser::is_word
+    Halt,      // "HALT", not reserved, see Parser::is_word
 }
 
 impl Type for Keyword {
@@ -16,6 +45,35 @@ impl Type for Keyword {
             Keyword::Program => "reserved keyword PROGRAM",
             Keyword::Var => "reserved keyword VAR",
             Keyword::Procedure => "reserved keyword PROCEDURE",
+            Keyword::If => "reserved keyword IF",
+            Keyword::Then => "reserved keyword THEN",
+            Keyword::Else => "reserved keyword ELSE",
+            Keyword::While => "reserved keyword WHILE",
+            Keyword::Do => "reserved keyword DO",
+            Keyword::Repeat => "reserved keyword REPEAT",
+            Keyword::Until => "reserved keyword UNTIL",
+            Keyword::For => "reserved keyword FOR",
+            Keyword::To => "reserved keyword TO",
+            Keyword::Downto => "reserved keyword DOWNTO",
+            Keyword::Case => "reserved keyword CASE",
+            Keyword::Of => "reserved keyword OF",
+            Keyword::Function => "reserved keyword FUNCTION",
+            Keyword::Const => "reserved keyword CONST",
+            Keyword::Out => "parameter mode OUT",
+            Keyword::Type => "reserved keyword TYPE",
+            Keyword::Array => "reserved keyword ARRAY",
+            Keyword::Record => "reserved keyword RECORD",
+            Keyword::String => "reserved keyword STRING",
+            Keyword::Set => "reserved keyword SET",
+            Keyword::Nil => "reserved keyword NIL",
+            Keyword::Forward => "directive FORWARD",
+            Keyword::Label => "reserved keyword LABEL",
+            Keyword::Goto => "reserved keyword GOTO",
+            Keyword::With => "reserved keyword WITH",
+            Keyword::Break => "predeclared BREAK",
+            Keyword::Continue => "predeclared CONTINUE",
+            Keyword::Exit => "predeclared EXIT",
+            Keyword::Halt => "predeclared HALT",
         }
     }
 }
@@ -28,6 +86,35 @@ impl Value for Keyword {
             Keyword::Program => "PROGRAM",
             Keyword::Var => "VAR",
             Keyword::Procedure => "PROCEDURE",
+            Keyword::If => "IF",
+            Keyword::Then => "THEN",
+            Keyword::Else => "ELSE",
+            Keyword::While => "WHILE",
+            Keyword::Do => "DO",
+            Keyword::Repeat => "REPEAT",
+            Keyword::Until => "UNTIL",
+            Keyword::For => "FOR",
+            Keyword::To => "TO",
+            Keyword::Downto => "DOWNTO",
+            Keyword::Case => "CASE",
+            Keyword::Of => "OF",
+            Keyword::Function => "FUNCTION",
+            Keyword::Const => "CONST",
+            Keyword::Out => "OUT",
+            Keyword::Type => "TYPE",
+            Keyword::Array => "ARRAY",
+            Keyword::Record => "RECORD",
+            Keyword::String => "STRING",
+            Keyword::Set => "SET",
+            Keyword::Nil => "NIL",
+            Keyword::Forward => "FORWARD",
+            Keyword::Label => "LABEL",
+            Keyword::Goto => "GOTO",
+            Keyword::With => "WITH",
+            Keyword::Break => "BREAK",
+            Keyword::Continue => "CONTINUE",
+            Keyword::Exit => "EXIT",
+            Keyword::Halt => "HALT",
         }
     }
 }
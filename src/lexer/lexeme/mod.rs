@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
+pub mod boolean;
 pub mod char;
+pub mod char_string;
 pub mod id;
 pub mod keyword;
 pub mod number;
@@ -7,6 +7,9 @@ use crate::{
 pub enum NumberType {
     Integer,
     Real,
+    Boolean,
+    Char,
+    String,
 }
 
 impl NumberType {
@@ -15,6 +18,12 @@ impl NumberType {
             return Ok(NumberType::Integer);
         } else if s == NumberType::Real.r#type() {
             return Ok(NumberType::Real);
+        } else if s == NumberType::Boolean.r#type() {
+            return Ok(NumberType::Boolean);
+        } else if s == NumberType::Char.r#type() {
+            return Ok(NumberType::Char);
+        } else if s == NumberType::String.r#type() {
+            return Ok(NumberType::String);
         }
         return Err(Error::InvalidSyntax);
     }
@@ -25,6 +34,9 @@ impl Clone for NumberType {
         match self {
             NumberType::Integer => NumberType::Integer,
             NumberType::Real => NumberType::Real,
+            NumberType::Boolean => NumberType::Boolean,
+            NumberType::Char => NumberType::Char,
+            NumberType::String => NumberType::String,
         }
     }
 }
@@ -34,6 +46,9 @@ impl Type for NumberType {
         match self {
             NumberType::Integer => "INTEGER",
             NumberType::Real => "REAL",
+            NumberType::Boolean => "BOOLEAN",
+            NumberType::Char => "CHAR",
+            NumberType::String => "STRING",
         }
     }
 }
@@ -5,7 +5,29 @@ pub enum Op {
     Sub,    // "-"
     Mul,    // "*"
     Div,    // "/"
+    IntDiv, // "DIV"
+    Mod,    // "MOD"
     Assign, // ":="
+    Eq,     // "="
+    NotEq,  // "<>"
+    Lt,     // "<"
+    Le,     // "<="
+    Gt,     // ">"
+    Ge,     // ">="
+    Range,  // ".."
+    And,    // "AND"
+    Or,     // "OR"
+    Not,    // "NOT"
+    In,     // "IN"
+}
+
+impl Op {
+    /// is_relational reports whether the op type compares two operands.
+    pub fn is_relational(r#type: &str) -> bool {
+        [Op::Eq, Op::NotEq, Op::Lt, Op::Le, Op::Gt, Op::Ge]
+            .iter()
+            .any(|op| op.equal_type(r#type))
+    }
 }
 
 impl Type for Op {
@@ -15,7 +37,20 @@ impl Type for Op {
             Op::Sub => "sub",
             Op::Mul => "mul",
             Op::Div => "div",
+            Op::IntDiv => "integer div",
+            Op::Mod => "mod",
             Op::Assign => "assign",
+            Op::Eq => "equal",
+            Op::NotEq => "not equal",
+            Op::Lt => "less than",
+            Op::Le => "less equal",
+            Op::Gt => "greater than",
+            Op::Ge => "greater equal",
+            Op::Range => "range",
+            Op::And => "and",
+            Op::Or => "or",
+            Op::Not => "not",
+            Op::In => "in",
         }
     }
 }
@@ -27,7 +62,20 @@ impl Value for Op {
             Op::Sub => "-",
             Op::Mul => "*",
             Op::Div => "/",
+            Op::IntDiv => "DIV",
+            Op::Mod => "MOD",
             Op::Assign => ":=",
+            Op::Eq => "=",
+            Op::NotEq => "<>",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Range => "..",
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Not => "NOT",
+            Op::In => "IN",
         }
     }
 }
@@ -1,15 +1,18 @@
-use self::lexeme::number::NumberType;
 use crate::token::Token;
 use crate::{error::Error, utils};
-use lexeme::{char::Char, id::ID, keyword::Keyword, number::Number, op::Op, Type, Value};
+use lexeme::{
+    boolean::Boolean, char::Char, char_string::CharString, id::ID, keyword::Keyword,
+    number::Number, op::Op, Type, Value,
+};
 
 pub mod lexeme;
 mod tests;
 
 #[derive(Debug)]
 pub struct Lexer {
-    code: Vec<char>, // The content of the input code.
-    pos: usize,      // The current position of the input code.
+    code: Vec<char>,        // The content of the input code.
+    pos: usize,             // The current position of the input code.
+    complete_boolean: bool, // Whether boolean expressions are fully evaluated, switched by {$B+} and {$B-}.
 }
 
 impl Lexer {
@@ -17,9 +20,16 @@ impl Lexer {
         Lexer {
             code: text.chars().collect::<Vec<char>>(),
             pos: 0,
+            complete_boolean: false,
         }
     }
 
+    /// complete_boolean reports whether AND and OR evaluate both operands, which is switched
+    /// on by the {$B+} directive and off by the {$B-} directive (the default).
+    pub fn complete_boolean(&self) -> bool {
+        self.complete_boolean
+    }
+
     pub fn get_next_token(&mut self) -> Result<Token, Error> {
         'l: while !Char::EOF.equal_value(self.current_char()) {
             /* Skip whitespace and '\n' */
@@ -37,6 +47,16 @@ impl Lexer {
                 continue;
             }
 
+            /* Character string scan */
+            if Char::Quote.equal_value(self.current_char())
+                || Char::Hash.equal_value(self.current_char())
+            {
+                match self.char_string() {
+                    Err(e) => return Err(self.wrap_error(e)),
+                    Ok(s) => return Ok(Token::new(s.r#type(), s.value())),
+                }
+            }
+
             /* Identifier scan */
             if utils::isalnum(&self.current_char()) {
                 return Ok(self.id());
@@ -59,22 +79,43 @@ impl Lexer {
                 return Ok(Token::new(Op::Assign.r#type(), Op::Assign.value()));
             }
 
-            /* '+', '-', '*', '/' scan */
-            for op in [Op::Add, Op::Sub, Op::Mul, Op::Div] {
+            /* '<>', '<=', '>=', '..' scan */
+            for op in [Op::NotEq, Op::Le, Op::Ge, Op::Range] {
+                if op.equal_value(self.current_pair()) {
+                    self.advance();
+                    self.advance();
+                    return Ok(Token::new(op.r#type(), op.value()));
+                }
+            }
+
+            /* '+', '-', '*', '/', '=', '<', '>' scan */
+            for op in [
+                Op::Add,
+                Op::Sub,
+                Op::Mul,
+                Op::Div,
+                Op::Eq,
+                Op::Lt, // Should after NotEq and Le.
+                Op::Gt, // Should after Ge.
+            ] {
                 if op.equal_value(self.current_char()) {
                     self.advance();
                     return Ok(Token::new(op.r#type(), op.value()));
                 }
             }
 
-            /* ';', '.', '(', ')', ':', ',' scan */
+            /* ';', '.', '(', ')', ':', ',', '[', ']', '^', '@' scan */
             for c in [
                 Char::Semi,
-                Char::Dot,
+                Char::Dot, // Should after Range.
                 Char::LeftParen,
                 Char::RightParen,
                 Char::Colon, // Should after Assign.
                 Char::Comma,
+                Char::LeftBracket,
+                Char::RightBracket,
+                Char::Caret,
+                Char::At,
             ] {
                 if c.equal_value(self.current_char()) {
                     self.advance();
@@ -88,13 +129,22 @@ impl Lexer {
         Ok(Token::new(Char::EOF.r#type(), Char::EOF.value()))
     }
 
+    /// peek_token returns the next token without consuming it.
+    pub fn peek_token(&mut self) -> Result<Token, Error> {
+        let (pos, complete_boolean) = (self.pos, self.complete_boolean);
+        let token = self.get_next_token();
+        self.pos = pos;
+        self.complete_boolean = complete_boolean;
+        token
+    }
+
     #[allow(dead_code)]
     pub fn print_all_token(&mut self) {
         let mut token: Token;
         loop {
             token = match self.get_next_token() {
                 Err(e) => {
-                    println!("get_next_token failed, error: {}", e);
+                    eprintln!("get_next_token failed, error: {}", e);
                     return;
                 }
                 Ok(t) => t,
@@ -125,10 +175,21 @@ impl Lexer {
     }
 
     fn skip_comment(&mut self) {
-        while self.current_char() != Char::RightBrace.char() {
+        let mut comment = String::from("");
+        while self.current_char() != Char::RightBrace.char()
+            && !Char::EOF.equal_value(self.current_char())
+        {
+            comment.push(self.current_char());
             self.advance();
         }
         self.advance();
+
+        /* Boolean evaluation directive */
+        match comment.trim().to_uppercase().as_str() {
+            "$B+" => self.complete_boolean = true,
+            "$B-" => self.complete_boolean = false,
+            _ => {}
+        }
     }
 
     fn number(&mut self) -> Result<Number, Error> {
@@ -139,7 +200,8 @@ impl Lexer {
             self.advance();
         }
 
-        if !Char::Dot.equal_value(self.current_char()) {
+        /* The '..' after an integer is a range, e.g. '1..10' */
+        if !Char::Dot.equal_value(self.current_char()) || self.peek() == Some(Char::Dot.char()) {
             return Ok(Number::Integer(val));
         }
 
@@ -158,6 +220,52 @@ impl Lexer {
         Ok(Number::Real(val))
     }
 
+    /// char_string handles the quoted strings and the character codes next to each other, e.g.
+    /// 'It''s'#10, a quote inside a quoted string is written twice.
+    fn char_string(&mut self) -> Result<CharString, Error> {
+        let mut val = String::from("");
+
+        loop {
+            if Char::Hash.equal_value(self.current_char()) {
+                self.advance();
+
+                let mut code = String::from("");
+                while utils::is_digit(&self.current_char()) {
+                    code.push(self.current_char());
+                    self.advance();
+                }
+
+                match code.parse::<u32>().ok().and_then(char::from_u32) {
+                    Some(c) => val.push(c),
+                    None => return Err(Error::InvalidSyntax),
+                }
+            } else if Char::Quote.equal_value(self.current_char()) {
+                self.advance();
+
+                loop {
+                    if self.pos >= self.code.len() || Char::LF.equal_value(self.current_char()) {
+                        return Err(Error::InvalidSyntax);
+                    }
+
+                    if Char::Quote.equal_value(self.current_char()) {
+                        if self.peek() != Some(Char::Quote.char()) {
+                            self.advance();
+                            break;
+                        }
+                        self.advance();
+                    }
+
+                    val.push(self.current_char());
+                    self.advance();
+                }
+            } else {
+                break;
+            }
+        }
+
+        Ok(CharString::new(val))
+    }
+
     /// peek returns the next char without increasing self.pos.
     fn peek(&self) -> Option<char> {
         let peek_pos = self.pos + 1;
@@ -168,6 +276,15 @@ impl Lexer {
         }
     }
 
+    /// current_pair returns the current char and the next char as a string.
+    fn current_pair(&self) -> String {
+        let mut pair = self.current_char().to_string();
+        if let Some(c) = self.peek() {
+            pair.push(c);
+        }
+        pair
+    }
+
     /// id handles identifiers and reserved keywords.
     fn id(&mut self) -> Token {
         let mut val = String::from("");
@@ -188,15 +305,44 @@ impl Lexer {
             Keyword::Program,
             Keyword::Var,
             Keyword::Procedure,
+            Keyword::If,
+            Keyword::Then,
+            Keyword::Else,
+            Keyword::While,
+            Keyword::Do,
+            Keyword::Repeat,
+            Keyword::Until,
+            Keyword::For,
+            Keyword::To,
+            Keyword::Downto,
+            Keyword::Case,
+            Keyword::Of,
+            Keyword::Function,
+            Keyword::Const,
+            Keyword::Type,
+            Keyword::Array,
+            Keyword::Record,
+            Keyword::String,
+            Keyword::Set,
+            Keyword::Nil,
+            Keyword::Label,
+            Keyword::Goto,
+            Keyword::With,
         ] {
             if k.equal_value(&val.to_uppercase()) {
                 return Token::new(k.r#type(), k.value());
             }
         }
 
-        for t in [NumberType::Integer, NumberType::Real] {
-            if t.equal_type(&val.to_uppercase()) {
-                return Token::new(t.r#type(), t.r#type());
+        for op in [Op::IntDiv, Op::Mod, Op::And, Op::Or, Op::Not, Op::In] {
+            if op.equal_value(&val.to_uppercase()) {
+                return Token::new(op.r#type(), op.value());
+            }
+        }
+
+        for b in [Boolean::True, Boolean::False] {
+            if b.equal_value(&val.to_uppercase()) {
+                return Token::new(b.r#type(), b.value());
             }
         }
 
@@ -204,7 +350,7 @@ impl Lexer {
     }
 
     fn wrap_error(&self, err: Error) -> Error {
-        println!(
+        eprintln!(
             "[lexer] get the next token failed, current char: {}, ascii: {}, pos: {}",
             self.current_char(),
             self.current_char() as u32,
@@ -3,13 +3,17 @@ mod tests {
     use crate::lexer::Lexer;
     use std::env;
     use std::fs;
+
+    const DEFAULT_TEST_CODE: &str = "./test-code/code-4.pas";
+
     #[test]
     fn test_lexer() {
         let args: Vec<String> = env::args().collect();
-        if args.len() == 0 {
-            panic!("code file not found");
-        }
-        let filename = &args[args.len() - 1];
+        /* Fall back to the default test code when no file is given, e.g. `cargo test` */
+        let filename = match args.last() {
+            Some(f) if f.ends_with(".pas") => f,
+            _ => DEFAULT_TEST_CODE,
+        };
 
         let code =
             fs::read_to_string(filename).expect("Something went wrong when reading the file");
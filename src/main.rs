@@ -1,8 +1,29 @@
+// The code base propagates errors with explicit `match` expressions and keeps
+// its own naming for lexemes, so these lints are allowed crate-wide.
+#![allow(
+    clippy::question_mark,
+    clippy::needless_return,
+    clippy::manual_map,
+    clippy::let_and_return,
+    clippy::redundant_pattern_matching,
+    clippy::module_inception,
+    clippy::upper_case_acronyms,
+    clippy::non_canonical_clone_impl,
+    clippy::redundant_static_lifetimes,
+    clippy::needless_borrow,
+    clippy::needless_borrows_for_generic_args,
+    clippy::expect_fun_call,
+    clippy::empty_line_after_doc_comments
+)]
+
 use core::panic;
 use error::Error;
-use std::{env, fs};
+use std::{env, fs, process};
 
 mod ast;
+mod builtin;
+mod console;
+mod data_type;
 mod error;
 mod global_scope;
 mod interpreter;
@@ -12,18 +33,19 @@ mod token;
 mod utils;
 
 fn main() {
-    /* Read code from file */
+    /* Read code from file, the scopes are dumped to the standard error with --debug */
     let mut args = env::args();
     args.next();
-    let file_name = match args.next() {
+    let (debug, args): (Vec<String>, Vec<String>) = args.partition(|a| a == "--debug");
+    let file_name = match args.into_iter().next() {
         Some(f) => f,
         None => panic!(
-            "{}, usage: ./pascal-interpreter ./code.pas", // TODO: need op
+            "{}, usage: ./pascal-interpreter [--debug] ./code.pas", // TODO: need op
             Error::FileNotFound
         ),
     };
 
-    println!("Read code from {}", file_name);
+    eprintln!("Read code from {}", file_name);
 
     let code = fs::read_to_string(file_name).expect("Something went wrong when reading the file");
 
@@ -35,7 +57,10 @@ fn main() {
         }
     };
 
-    if let Err(e) = interpreter.execute() {
-        panic!("Interpreter execute failed, error: {}", e);
+    interpreter.set_debug(!debug.is_empty());
+
+    match interpreter.execute() {
+        Ok(code) => process::exit(code),
+        Err(e) => panic!("Interpreter execute failed, error: {}", e),
     }
 }
@@ -1,27 +1,74 @@
 use crate::ast::block::Block;
+use crate::ast::case_statement::{Case, CaseElement, CaseLabel};
+use crate::ast::const_decl::ConstDecl;
 use crate::ast::declaration::Declaration;
+use crate::ast::exit_statement::Exit;
+use crate::ast::field_width::FieldWidth;
+use crate::ast::for_statement::For;
+use crate::ast::goto_statement::Goto;
+use crate::ast::halt_statement::Halt;
+use crate::ast::if_statement::If;
+use crate::ast::logical_op::LogicalOp;
+use crate::ast::loop_control::LoopControl;
 use crate::ast::procedure::Procedure;
 use crate::ast::procedure_call::ProcedureCall;
-use crate::ast::var_decl::VarDecl;
+use crate::ast::repeat_statement::Repeat;
+use crate::ast::set_constructor::{SetConstructor, SetElement};
+use crate::ast::type_decl::TypeDecl;
+use crate::ast::type_spec::TypeSpec;
+use crate::ast::var_decl::{PassMode, VarDecl};
+use crate::ast::while_statement::While;
+use crate::ast::with_statement::With;
 use crate::ast::{
-    assign::Assign, bin_op::BinOp, compound::Compound, integer::Integer, no_op::NoOp,
-    program::Program, real::Real, unary_op::UnaryOp, var::Var, Node,
+    self, assign::Assign, bin_op::BinOp, compound::Compound, integer::Integer, no_op::NoOp,
+    program::Program, real::Real, unary_op::UnaryOp, var::Selector, var::Var, Node, NodeType,
 };
+use crate::builtin::Builtin;
 use crate::error::Error;
+use crate::global_scope::Signal;
 use crate::lexer::lexeme::{
-    char::Char, id::ID, keyword::Keyword, number::NumberType, op::Op, Type, Value,
+    boolean::Boolean, char::Char, id::ID, keyword::Keyword, number::NumberType, op::Op, Type, Value,
 };
 use crate::lexer::Lexer;
 use crate::token::Token;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::time::SystemTime;
 
 mod tests;
 
-#[derive(Debug)]
+/// Labels are the labels declared in a block, the compound statement holding the statement with
+/// each label and the compound statements enclosing each GOTO.
+struct Labels {
+    declared: HashMap<u32, Option<usize>>,
+    gotos: Vec<(u32, Vec<usize>)>,
+}
+
+impl Labels {
+    fn new() -> Labels {
+        Labels {
+            declared: HashMap::new(),
+            gotos: Vec::new(),
+        }
+    }
+}
+
+/// Header is the formal parameters and the return type of a procedure declared FORWARD.
+type Header = (Vec<Rc<VarDecl>>, Option<Rc<TypeSpec>>);
+
 pub struct Parser {
     lexer: Lexer,
     current_token: Token,
+    control_variables: Vec<String>, // The control variables of the enclosing FOR statements.
+    routines: Vec<HashMap<String, Vec<PassMode>>>, // The parameter modes of the visible procedures, one map per block.
+    forwards: Vec<HashMap<String, Header>>, // The procedures declared FORWARD without a block yet, one map per block.
+    labels: Vec<Labels>, // The labels of the enclosing blocks, one table per block.
+    compounds: Vec<usize>, // The compound statements enclosing the statement being parsed.
+    compound_count: usize, // The number of compound statements parsed, which numbers them.
+    loops: usize,        // The number of the loops enclosing the statement being parsed.
+    functions: Vec<bool>, // Whether each enclosing procedure is a function, one flag per procedure.
+    types: Vec<HashMap<String, Rc<TypeSpec>>>, // The types declared in the enclosing blocks, one map per block.
+    variables: Vec<HashMap<String, Rc<TypeSpec>>>, // The declared types of the visible variables, one map per block, parameter list or WITH record.
 }
 
 impl Parser {
@@ -29,6 +76,16 @@ impl Parser {
         Parser {
             lexer,
             current_token: Token::new(Char::EOF.r#type(), Char::EOF.value()),
+            control_variables: Vec::new(),
+            routines: Vec::new(),
+            forwards: Vec::new(),
+            labels: Vec::new(),
+            compounds: Vec::new(),
+            compound_count: 0,
+            loops: 0,
+            functions: Vec::new(),
+            types: Vec::new(),
+            variables: Vec::new(),
         }
     }
 
@@ -49,14 +106,14 @@ impl Parser {
         };
 
         if !Char::EOF.equal_type(self.current_token.r#type()) {
-            println!(
+            eprintln!(
                 "[parser] [parse] current token {} is not EOF",
                 self.current_token
             );
             return Err(Error::InvalidSyntax);
         }
 
-        println!(
+        eprintln!(
             "[parser] [parse] time used: {}us",
             SystemTime::now().duration_since(begin).unwrap().as_micros()
         );
@@ -82,6 +139,13 @@ impl Parser {
         }
     }
 
+    /// is_word reports whether the current token is the identifier spelled as the keyword, the
+    /// directives and the predeclared routines are not reserved and may be used as names.
+    fn is_word(&self, keyword: Keyword) -> bool {
+        self.current_token.r#type() == ID
+            && keyword.equal_value(self.current_token.value().to_uppercase())
+    }
+
     /// BNF:
     /// program: (PROGRAM id SEMI)? block DOT
     fn program(&mut self) -> Result<Rc<dyn Node>, Error> {
@@ -96,7 +160,7 @@ impl Parser {
                 Ok(name_op) => match name_op {
                     Some(name) => name,
                     None => {
-                        println!(
+                        eprintln!(
                             "[parser] [program] variable's name not found, current token: {}",
                             self.current_token
                         );
@@ -123,15 +187,24 @@ impl Parser {
     }
 
     /// BNF:
-    /// procedure: PROCEDURE id (LPAREN formal_parameter_list RPAREN)? SEMI block SEMI
-    fn procedure(&mut self) -> Result<Procedure, Error> {
-        self.eat(Keyword::Procedure.r#type());
+    /// procedure: PROCEDURE id (LPAREN formal_parameter_list RPAREN)? SEMI (block | FORWARD) SEMI
+    ///          | FUNCTION id (LPAREN formal_parameter_list RPAREN)? COLON type_spec SEMI (block | FORWARD) SEMI
+    ///
+    /// The header of a procedure declared FORWARD may be left out or repeated when its block is
+    /// declared, e.g. `PROCEDURE id SEMI block SEMI`, a repeated header must match.
+    fn procedure(&mut self) -> Result<Option<Procedure>, Error> {
+        let is_function = Keyword::Function.equal_type(self.current_token.r#type());
+        if is_function {
+            self.eat(Keyword::Function.r#type());
+        } else {
+            self.eat(Keyword::Procedure.r#type());
+        }
 
         let name = match self.variable().name() {
             Ok(name_op) => match name_op {
                 Some(name) => name,
                 None => {
-                    println!(
+                    eprintln!(
                         "[parser] [procedure] variable's name not found, current token: {}",
                         self.current_token
                     );
@@ -141,8 +214,15 @@ impl Parser {
             Err(e) => return Err(e),
         };
 
+        let forward = self
+            .forwards
+            .last_mut()
+            .and_then(|f| f.remove(&name.to_uppercase()));
+
         let mut var_decl_list: Vec<Rc<VarDecl>> = Vec::new();
+        let mut has_header = false;
         if Char::LeftParen.equal_type(self.current_token.r#type()) {
+            has_header = true;
             self.eat(Char::LeftParen.r#type());
             match self.formal_parameter_list() {
                 Ok(vds) => vds.iter().for_each(|vd| var_decl_list.push(vd.clone())),
@@ -151,23 +231,110 @@ impl Parser {
             self.eat(Char::RightParen.r#type());
         }
 
+        let mut return_type: Option<Rc<TypeSpec>> = None;
+        if is_function && (forward.is_none() || Char::Colon.equal_type(self.current_token.r#type()))
+        {
+            has_header = true;
+            self.eat(Char::Colon.r#type());
+            match self.type_spec() {
+                Ok(t) => return_type = Some(t),
+                Err(e) => return Err(e),
+            };
+        }
+
+        /* The block of a procedure declared FORWARD has the header of the declaration */
+        if let Some((forward_params, forward_return)) = forward {
+            let matched = if has_header {
+                Parser::same_header(&var_decl_list, &forward_params)
+                    && return_type.as_ref().and_then(|t| t.name())
+                        == forward_return.as_ref().and_then(|t| t.name())
+            } else {
+                is_function == forward_return.is_some()
+            };
+            if !matched {
+                eprintln!(
+                    "[parser] [procedure] header of '{}' does not match its forward declaration",
+                    name
+                );
+                return Err(Error::ForwardMismatch);
+            }
+            var_decl_list = forward_params;
+            return_type = forward_return;
+        }
+
+        /* Register the parameters before the block so that recursive calls can be checked */
+        let modes = var_decl_list
+            .iter()
+            .flat_map(|vd| vd.ids().into_iter().map(|_| vd.mode()))
+            .collect();
+        if let Some(routines) = self.routines.last_mut() {
+            routines.insert(name.to_uppercase(), modes);
+        }
+
         self.eat(Char::Semi.r#type());
-        let block = match self.block() {
+
+        /* FORWARD is a directive, a block never starts with an identifier */
+        if self.is_word(Keyword::Forward) {
+            self.eat(ID);
+            self.eat(Char::Semi.r#type());
+
+            if let Some(forwards) = self.forwards.last_mut() {
+                forwards.insert(name.to_uppercase(), (var_decl_list, return_type));
+            }
+            return Ok(None);
+        }
+
+        let params = var_decl_list
+            .iter()
+            .flat_map(|vd| {
+                vd.ids()
+                    .into_iter()
+                    .map(|id| (id.to_uppercase(), vd.type_spec()))
+            })
+            .collect();
+        self.functions.push(return_type.is_some());
+        self.variables.push(params);
+        let block = self.block();
+        self.variables.pop();
+        self.functions.pop();
+        let block = match block {
             Ok(block) => block,
             Err(e) => return Err(e),
         };
         self.eat(Char::Semi.r#type());
 
-        Ok(Procedure::new(&name, var_decl_list, Rc::new(block)))
+        Ok(Some(Procedure::new(
+            &name,
+            var_decl_list,
+            return_type,
+            Rc::new(block),
+        )))
+    }
+
+    /// same_header reports whether the formal parameters have the same names, modes and type
+    /// names in the same order.
+    fn same_header(a: &[Rc<VarDecl>], b: &[Rc<VarDecl>]) -> bool {
+        let params = |vds: &[Rc<VarDecl>]| -> Vec<(String, PassMode, Option<String>)> {
+            vds.iter()
+                .flat_map(|vd| {
+                    vd.ids()
+                        .into_iter()
+                        .map(move |id| (id.to_uppercase(), vd.mode(), vd.type_spec().name()))
+                })
+                .collect()
+        };
+
+        let (a, b) = (params(a), params(b));
+        a == b && a.iter().all(|(_, _, t)| t.is_some())
     }
 
     /// BNF:
-    /// formal_parameter_list: variable_declaration
-    ///                      | variable_declaration SEMI formal_parameter_list
+    /// formal_parameter_list: formal_parameter
+    ///                      | formal_parameter SEMI formal_parameter_list
     fn formal_parameter_list(&mut self) -> Result<Vec<Rc<VarDecl>>, Error> {
         let mut var_decls: Vec<Rc<VarDecl>> = Vec::new();
 
-        match self.variable_declaration() {
+        match self.formal_parameter() {
             Ok(var_decl) => var_decls.push(Rc::new(var_decl)),
             Err(e) => return Err(e),
         };
@@ -185,9 +352,93 @@ impl Parser {
         return Ok(var_decls);
     }
 
+    /// BNF:
+    /// formal_parameter: (VAR | CONST | OUT)? ID (COMMA ID)* COLON type_spec
+    fn formal_parameter(&mut self) -> Result<VarDecl, Error> {
+        let mut mode = PassMode::Value;
+        for (k, m) in [
+            (Keyword::Var, PassMode::Var),
+            (Keyword::Const, PassMode::Const),
+        ] {
+            if k.equal_type(self.current_token.r#type()) {
+                self.eat(k.r#type());
+                mode = m;
+                break;
+            }
+        }
+
+        /* OUT is a mode only when a parameter name follows, otherwise it is the name */
+        if mode == PassMode::Value && self.is_word(Keyword::Out) {
+            match self.lexer.peek_token() {
+                Ok(t) if t.r#type() == ID => {
+                    self.eat(ID);
+                    mode = PassMode::Out;
+                }
+                Ok(_) => {}
+                Err(e) => return Err(e),
+            };
+        }
+
+        match self.variable_declaration() {
+            Ok(vd) => Ok(VarDecl::parameter(vd.ids(), vd.type_spec(), mode)),
+            Err(e) => Err(e),
+        }
+    }
+
     /// BNF:
     /// block: declarations compound_statement
     fn block(&mut self) -> Result<Block, Error> {
+        self.routines.push(HashMap::new());
+        self.forwards.push(HashMap::new());
+        self.labels.push(Labels::new());
+        self.types.push(HashMap::new());
+        self.variables.push(HashMap::new());
+        let block = self.block_body();
+        self.variables.pop();
+        self.types.pop();
+        let labels = self.labels.pop();
+        self.forwards.pop();
+        self.routines.pop();
+
+        let block = match block {
+            Ok(b) => b,
+            Err(e) => return Err(e),
+        };
+        if let Some(labels) = labels {
+            if let Err(e) = Parser::check_labels(&labels) {
+                return Err(e);
+            }
+        }
+        Ok(block)
+    }
+
+    /// check_labels checks the GOTO statements of the block. The statement with the label must be
+    /// in a compound statement enclosing the GOTO, so a GOTO never jumps into a structured
+    /// statement.
+    fn check_labels(labels: &Labels) -> Result<(), Error> {
+        for (label, compounds) in labels.gotos.iter() {
+            match labels.declared.get(label) {
+                Some(Some(compound)) if compounds.contains(compound) => {}
+                Some(Some(_)) => {
+                    eprintln!(
+                        "[parser] [check_labels] GOTO {} jumps into a structured statement",
+                        label
+                    );
+                    return Err(Error::InvalidGoto);
+                }
+                _ => {
+                    eprintln!(
+                        "[parser] [check_labels] no statement has the label {}",
+                        label
+                    );
+                    return Err(Error::LabelNotFound);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn block_body(&mut self) -> Result<Block, Error> {
         let declaration = match self.declarations() {
             Ok(d) => d,
             Err(e) => return Err(e),
@@ -202,33 +453,154 @@ impl Parser {
     }
 
     /// BNF:
-    /// declarations: (VAR (variable_declaration SEMI)+)* (procedure)*
+    /// declarations: (LABEL label (COMMA label)* SEMI
+    ///               | CONST (constant_declaration SEMI)+
+    ///               | TYPE (type_declaration SEMI)+
+    ///               | VAR (variable_declaration SEMI)+)* (procedure)*
     ///             | empty
     fn declarations(&mut self) -> Result<Declaration, Error> {
         let mut declaration = Declaration::new(Vec::new(), Vec::new());
 
-        while Keyword::Var.equal_type(self.current_token.r#type()) {
-            self.eat(Keyword::Var.r#type());
+        loop {
+            if Keyword::Label.equal_type(self.current_token.r#type()) {
+                self.eat(Keyword::Label.r#type());
+
+                loop {
+                    let label = match self.label() {
+                        Ok(l) => l,
+                        Err(e) => return Err(e),
+                    };
+                    if let Some(labels) = self.labels.last_mut() {
+                        if labels.declared.insert(label, None).is_some() {
+                            eprintln!("[parser] [declarations] label {} was redefined", label);
+                            return Err(Error::LabelRedefined);
+                        }
+                    }
 
-            while self.current_token.r#type() == ID {
-                match self.variable_declaration() {
-                    Ok(vd) => declaration.var_decl_list_push(Rc::new(vd)),
-                    Err(e) => return Err(e),
-                };
+                    if !Char::Comma.equal_type(self.current_token.r#type()) {
+                        break;
+                    }
+                    self.eat(Char::Comma.r#type());
+                }
                 self.eat(Char::Semi.r#type());
+            } else if Keyword::Const.equal_type(self.current_token.r#type()) {
+                self.eat(Keyword::Const.r#type());
+
+                while self.current_token.r#type() == ID {
+                    match self.constant_declaration() {
+                        Ok(cd) => declaration.const_decl_list_push(Rc::new(cd)),
+                        Err(e) => return Err(e),
+                    };
+                    self.eat(Char::Semi.r#type());
+                }
+            } else if Keyword::Type.equal_type(self.current_token.r#type()) {
+                self.eat(Keyword::Type.r#type());
+
+                while self.current_token.r#type() == ID {
+                    match self.type_declaration() {
+                        Ok(td) => declaration.type_decl_list_push(Rc::new(td)),
+                        Err(e) => return Err(e),
+                    };
+                    self.eat(Char::Semi.r#type());
+                }
+            } else if Keyword::Var.equal_type(self.current_token.r#type()) {
+                self.eat(Keyword::Var.r#type());
+
+                while self.current_token.r#type() == ID {
+                    let vd = match self.variable_declaration() {
+                        Ok(vd) => vd,
+                        Err(e) => return Err(e),
+                    };
+                    if let Some(variables) = self.variables.last_mut() {
+                        for id in vd.ids() {
+                            variables.insert(id.to_uppercase(), vd.type_spec());
+                        }
+                    }
+                    declaration.var_decl_list_push(Rc::new(vd));
+                    self.eat(Char::Semi.r#type());
+                }
+            } else {
+                break;
             }
         }
 
-        while Keyword::Procedure.equal_type(self.current_token.r#type()) {
+        while Keyword::Procedure.equal_type(self.current_token.r#type())
+            || Keyword::Function.equal_type(self.current_token.r#type())
+        {
             match self.procedure() {
-                Ok(p) => declaration.procedure_list_push(Rc::new(p)),
+                Ok(Some(p)) => declaration.procedure_list_push(Rc::new(p)),
+                Ok(None) => {}
                 Err(e) => return Err(e),
             };
         }
 
+        /* Every procedure declared FORWARD must be declared with its block in the same block */
+        if let Some(name) = self.forwards.last().and_then(|f| f.keys().next()) {
+            eprintln!(
+                "[parser] [declarations] block of forward declared '{}' not found",
+                name
+            );
+            return Err(Error::ProcedureNotFound);
+        }
+
         Ok(declaration)
     }
 
+    /// BNF:
+    /// constant_declaration: ID EQ expr
+    fn constant_declaration(&mut self) -> Result<ConstDecl, Error> {
+        let name = match self.variable().name() {
+            Ok(name_op) => match name_op {
+                Some(name) => name,
+                None => {
+                    eprintln!(
+                        "[parser] [constant_declaration] constant's name not found, current token: {}",
+                        self.current_token
+                    );
+                    return Err(Error::VarNotFound);
+                }
+            },
+            Err(e) => return Err(e),
+        };
+
+        self.eat(Op::Eq.r#type());
+
+        match self.expr() {
+            Ok(n) => Ok(ConstDecl::new(&name, n)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// BNF:
+    /// type_declaration: ID EQ type_spec
+    fn type_declaration(&mut self) -> Result<TypeDecl, Error> {
+        let name = match self.variable().name() {
+            Ok(name_op) => match name_op {
+                Some(name) => name,
+                None => {
+                    eprintln!(
+                        "[parser] [type_declaration] type's name not found, current token: {}",
+                        self.current_token
+                    );
+                    return Err(Error::VarNotFound);
+                }
+            },
+            Err(e) => return Err(e),
+        };
+
+        self.eat(Op::Eq.r#type());
+
+        let ts = match self.type_spec() {
+            Ok(ts) => ts,
+            Err(e) => return Err(e),
+        };
+        if let Some(types) = self.types.last_mut() {
+            types.insert(name.to_uppercase(), ts.clone());
+        }
+
+        Ok(TypeDecl::new(&name, ts))
+    }
+
     /// BNF:
     /// variable_declaration: ID (COMMA ID)* COLON type_spec
     fn variable_declaration(&mut self) -> Result<VarDecl, Error> {
@@ -238,7 +610,7 @@ impl Parser {
             Ok(name_op) => match name_op {
                 Some(id) => ids.push(id),
                 None => {
-                    println!(
+                    eprintln!(
                         "[parser] [variable_declaration] variable's name not found, current token: {}",
                         self.current_token
                     );
@@ -254,7 +626,7 @@ impl Parser {
                 Ok(name_op) => match name_op {
                     Some(id) => ids.push(id),
                     None => {
-                        println!(
+                        eprintln!(
                             "[parser] [variable_declaration] variable's name not found, current token: {}",
                             self.current_token
                         );
@@ -267,39 +639,286 @@ impl Parser {
 
         self.eat(Char::Colon.r#type());
 
-        let type_spec: NumberType;
-        if NumberType::Integer.equal_type(self.current_token.r#type()) {
-            self.eat(NumberType::Integer.r#type());
-            type_spec = NumberType::Integer;
-        } else if NumberType::Real.equal_type(self.current_token.r#type()) {
-            self.eat(NumberType::Real.r#type());
-            type_spec = NumberType::Real;
-        } else {
-            println!(
-                "[parser] [variable_declaration] current token '{}' is invalid, Real or Integer is required",
+        match self.type_spec() {
+            Ok(type_spec) => Ok(VarDecl::new(ids, type_spec)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// BNF:
+    /// type_spec: string_type
+    ///          | array_type
+    ///          | record_type
+    ///          | set_type
+    ///          | pointer_type
+    ///          | enumerated_type
+    ///          | subrange_type
+    fn type_spec(&mut self) -> Result<Rc<TypeSpec>, Error> {
+        if Keyword::String.equal_type(self.current_token.r#type()) {
+            return self.string_type();
+        }
+
+        if Keyword::Array.equal_type(self.current_token.r#type()) {
+            return self.array_type();
+        }
+
+        if Keyword::Record.equal_type(self.current_token.r#type()) {
+            return self.record_type();
+        }
+
+        if Keyword::Set.equal_type(self.current_token.r#type()) {
+            return self.set_type();
+        }
+
+        if Char::Caret.equal_type(self.current_token.r#type()) {
+            return self.pointer_type();
+        }
+
+        if Char::LeftParen.equal_type(self.current_token.r#type()) {
+            return self.enumerated_type();
+        }
+
+        self.subrange_type()
+    }
+
+    /// BNF:
+    /// set_type: SET OF type_spec
+    fn set_type(&mut self) -> Result<Rc<TypeSpec>, Error> {
+        self.eat(Keyword::Set.r#type());
+        self.eat(Keyword::Of.r#type());
+
+        match self.type_spec() {
+            Ok(base) => Ok(Rc::new(TypeSpec::Set(base))),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// BNF:
+    /// pointer_type: CARET ID
+    fn pointer_type(&mut self) -> Result<Rc<TypeSpec>, Error> {
+        self.eat(Char::Caret.r#type());
+
+        let name = self.current_token.value().to_string();
+        self.eat(ID);
+
+        Ok(Rc::new(TypeSpec::Pointer(name)))
+    }
+
+    /// BNF:
+    /// enumerated_type: LPAREN ID (COMMA ID)* RPAREN
+    fn enumerated_type(&mut self) -> Result<Rc<TypeSpec>, Error> {
+        let mut names = Vec::new();
+
+        self.eat(Char::LeftParen.r#type());
+        names.push(self.current_token.value().to_string());
+        self.eat(ID);
+
+        while Char::Comma.equal_type(self.current_token.r#type()) {
+            self.eat(Char::Comma.r#type());
+            names.push(self.current_token.value().to_string());
+            self.eat(ID);
+        }
+
+        self.eat(Char::RightParen.r#type());
+
+        Ok(Rc::new(TypeSpec::Enum(names)))
+    }
+
+    /// BNF:
+    /// string_type: STRING (LBRACKET expr RBRACKET)?
+    fn string_type(&mut self) -> Result<Rc<TypeSpec>, Error> {
+        self.eat(Keyword::String.r#type());
+
+        if !Char::LeftBracket.equal_type(self.current_token.r#type()) {
+            return Ok(Rc::new(TypeSpec::String(None)));
+        }
+
+        self.eat(Char::LeftBracket.r#type());
+        let len = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+        self.eat(Char::RightBracket.r#type());
+
+        Ok(Rc::new(TypeSpec::String(Some(len))))
+    }
+
+    /// BNF:
+    /// array_type: ARRAY LBRACKET type_spec (COMMA type_spec)* RBRACKET OF type_spec
+    fn array_type(&mut self) -> Result<Rc<TypeSpec>, Error> {
+        let mut indexes = Vec::new();
+
+        self.eat(Keyword::Array.r#type());
+        self.eat(Char::LeftBracket.r#type());
+
+        match self.type_spec() {
+            Ok(ts) => indexes.push(ts),
+            Err(e) => return Err(e),
+        };
+
+        while Char::Comma.equal_type(self.current_token.r#type()) {
+            self.eat(Char::Comma.r#type());
+            match self.type_spec() {
+                Ok(ts) => indexes.push(ts),
+                Err(e) => return Err(e),
+            };
+        }
+
+        self.eat(Char::RightBracket.r#type());
+        self.eat(Keyword::Of.r#type());
+
+        match self.type_spec() {
+            Ok(element) => Ok(Rc::new(TypeSpec::Array(indexes, element))),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// BNF:
+    /// record_type: RECORD variable_declaration (SEMI variable_declaration)* SEMI? END
+    fn record_type(&mut self) -> Result<Rc<TypeSpec>, Error> {
+        let mut fields = Vec::new();
+
+        self.eat(Keyword::Record.r#type());
+
+        match self.variable_declaration() {
+            Ok(vd) => fields.push(Rc::new(vd)),
+            Err(e) => return Err(e),
+        };
+
+        while Char::Semi.equal_type(self.current_token.r#type()) {
+            self.eat(Char::Semi.r#type());
+            if Keyword::End.equal_type(self.current_token.r#type()) {
+                break;
+            }
+            match self.variable_declaration() {
+                Ok(vd) => fields.push(Rc::new(vd)),
+                Err(e) => return Err(e),
+            };
+        }
+
+        self.eat(Keyword::End.r#type());
+
+        Ok(Rc::new(TypeSpec::Record(fields)))
+    }
+
+    /// BNF:
+    /// subrange_type: expr RANGE expr
+    ///              | ID
+    fn subrange_type(&mut self) -> Result<Rc<TypeSpec>, Error> {
+        let low = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        if Op::Range.equal_type(self.current_token.r#type()) {
+            self.eat(Op::Range.r#type());
+            return match self.expr() {
+                Ok(high) => Ok(Rc::new(TypeSpec::Range(low, high))),
+                Err(e) => Err(e),
+            };
+        }
+
+        /* A type without bounds is the name of a type */
+        if !matches!(low.r#type(), NodeType::Var) {
+            eprintln!(
+                "[parser] [subrange_type] current token '{}' is invalid, '..' is required",
                 self.current_token
             );
             return Err(Error::InvalidSyntax);
         }
 
-        Ok(VarDecl::new(ids, type_spec))
+        match low.name() {
+            Ok(Some(name)) => Ok(Rc::new(TypeSpec::Named(name))),
+            Ok(None) => Err(Error::TypeNotFound),
+            Err(e) => Err(e),
+        }
     }
 
     /// BNF:
-    /// compound_statement: BEGIN statement_list END
+    /// compound_statement: BEGIN labelled_statement (SEMI labelled_statement)* END
+    /// labelled_statement: (label COLON)? statement
     fn compound_statement(&mut self) -> Result<Compound, Error> {
+        self.compound_count += 1;
+        let id = self.compound_count;
+
+        self.compounds.push(id);
+        let compound = self.compound_body(id);
+        self.compounds.pop();
+        compound
+    }
+
+    fn compound_body(&mut self, id: usize) -> Result<Compound, Error> {
         let mut children = Vec::<Rc<dyn Node>>::new();
+        let mut labels = HashMap::new();
 
         self.eat(Keyword::Begin.r#type());
 
-        match self.statement_list() {
-            Ok(nodes) => nodes.iter().for_each(|n| children.push(n.clone())),
-            Err(e) => return Err(e),
-        };
+        loop {
+            if NumberType::Integer.equal_type(self.current_token.r#type()) {
+                let label = match self.label() {
+                    Ok(l) => l,
+                    Err(e) => return Err(e),
+                };
+                self.eat(Char::Colon.r#type());
+
+                /* The label must be declared in the block and mark a single statement */
+                let declared = self
+                    .labels
+                    .last_mut()
+                    .and_then(|l| l.declared.get_mut(&label));
+                match declared {
+                    Some(compound @ None) => *compound = Some(id),
+                    Some(Some(_)) => {
+                        eprintln!(
+                            "[parser] [compound_statement] label {} was redefined",
+                            label
+                        );
+                        return Err(Error::LabelRedefined);
+                    }
+                    None => {
+                        eprintln!(
+                            "[parser] [compound_statement] label {} is not declared",
+                            label
+                        );
+                        return Err(Error::LabelNotFound);
+                    }
+                };
+                labels.insert(label, children.len());
+            }
+
+            match self.statement() {
+                Ok(n) => children.push(n),
+                Err(e) => return Err(e),
+            };
+
+            if !Char::Semi.equal_type(self.current_token.r#type()) {
+                break;
+            }
+            self.eat(Char::Semi.r#type());
+        }
 
         self.eat(Keyword::End.r#type());
 
-        Ok(Compound::new(children))
+        Ok(Compound::new(children, labels))
+    }
+
+    /// BNF:
+    /// label: INTEGER
+    ///
+    /// A label is a number in 0..9999.
+    fn label(&mut self) -> Result<u32, Error> {
+        let label = match self.current_token.value().parse::<u32>() {
+            Ok(n) if NumberType::Integer.equal_type(self.current_token.r#type()) && n <= 9999 => n,
+            _ => {
+                eprintln!(
+                    "[parser] [label] current token '{}' is not a label",
+                    self.current_token
+                );
+                return Err(Error::InvalidSyntax);
+            }
+        };
+        self.eat(NumberType::Integer.r#type());
+        Ok(label)
     }
 
     /// BNF:
@@ -324,33 +943,383 @@ impl Parser {
     }
 
     /// BNF:
-    /// statement: compound_statement | assignment_statement | procedure_call_statement | empty
+    /// statement: compound_statement
+    ///          | if_statement
+    ///          | while_statement
+    ///          | repeat_statement
+    ///          | for_statement
+    ///          | case_statement
+    ///          | goto_statement
+    ///          | with_statement
+    ///          | loop_control_statement
+    ///          | exit_statement
+    ///          | halt_statement
+    ///          | assignment_statement
+    ///          | procedure_call_statement
+    ///          | empty
     fn statement(&mut self) -> Result<Rc<dyn Node>, Error> {
         if Keyword::Begin.equal_type(self.current_token.r#type()) {
             match self.compound_statement() {
                 Ok(cs) => Ok(Rc::new(cs)),
                 Err(e) => return Err(e),
             }
+        } else if Keyword::If.equal_type(self.current_token.r#type()) {
+            self.if_statement()
+        } else if Keyword::While.equal_type(self.current_token.r#type()) {
+            self.while_statement()
+        } else if Keyword::Repeat.equal_type(self.current_token.r#type()) {
+            self.repeat_statement()
+        } else if Keyword::For.equal_type(self.current_token.r#type()) {
+            self.for_statement()
+        } else if Keyword::Case.equal_type(self.current_token.r#type()) {
+            self.case_statement()
+        } else if Keyword::Goto.equal_type(self.current_token.r#type()) {
+            self.goto_statement()
+        } else if Keyword::With.equal_type(self.current_token.r#type()) {
+            self.with_statement()
         } else if self.current_token.r#type() == ID {
-            if Char::LeftParen.equal_value(self.lexer.current_char()) {
-                self.procedure_call()
-            } else {
+            let assignment = match self.is_assignment() {
+                Ok(a) => a,
+                Err(e) => return Err(e),
+            };
+
+            /* The predeclared routines are hidden by the routines declared with the same name */
+            if !assignment && !self.is_declared(self.current_token.value()) {
+                if self.is_word(Keyword::Break) || self.is_word(Keyword::Continue) {
+                    return self.loop_control_statement();
+                } else if self.is_word(Keyword::Exit) {
+                    return self.exit_statement();
+                } else if self.is_word(Keyword::Halt) {
+                    return self.halt_statement();
+                }
+            }
+
+            /* Any statement starting with an identifier which is not assigned is a call */
+            if assignment {
                 self.assginment_statement()
+            } else {
+                self.procedure_call()
             }
         } else {
             Ok(self.empty())
         }
     }
 
+    /// is_assignment reports whether the identifier starting the statement is assigned, which is
+    /// followed by ASSIGN or by a selector of a designator.
+    fn is_assignment(&mut self) -> Result<bool, Error> {
+        match self.lexer.peek_token() {
+            Ok(t) => Ok([
+                Op::Assign.r#type(),
+                Char::LeftBracket.r#type(),
+                Char::Dot.r#type(),
+                Char::Caret.r#type(),
+            ]
+            .contains(&t.r#type())),
+            Err(e) => Err(e),
+        }
+    }
+
     /// BNF:
-    /// procedure_call_statement: id LPAREN (expr (COMMA expr)*)? RPAREN
-    fn procedure_call(&mut self) -> Result<Rc<dyn Node>, Error> {
-        let name = match self.variable().name() {
+    /// goto_statement: GOTO label
+    fn goto_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        self.eat(Keyword::Goto.r#type());
+
+        let label = match self.label() {
+            Ok(l) => l,
+            Err(e) => return Err(e),
+        };
+
+        /* Where the label is allowed to be is checked at the end of the block */
+        match self.labels.last_mut() {
+            Some(labels) if labels.declared.contains_key(&label) => {
+                labels.gotos.push((label, self.compounds.clone()))
+            }
+            _ => {
+                eprintln!("[parser] [goto_statement] label {} is not declared", label);
+                return Err(Error::LabelNotFound);
+            }
+        };
+
+        Ok(Rc::new(Goto::new(label)))
+    }
+
+    /// BNF:
+    /// with_statement: WITH designator (COMMA designator)* DO statement
+    fn with_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        self.eat(Keyword::With.r#type());
+
+        let mut records = Vec::new();
+        let control_variables = self.control_variables.clone();
+        loop {
+            let record = match self.designator() {
+                Ok(v) => v,
+                Err(e) => return Err(e),
+            };
+
+            /* The fields of the record hide the variables with the same names, a field named like
+             * a control variable can be assigned */
+            let fields = self.fields(&record);
+            self.control_variables
+                .retain(|name| !fields.contains_key(name));
+            self.variables.push(fields);
+            records.push(record);
+
+            if !Char::Comma.equal_type(self.current_token.r#type()) {
+                break;
+            }
+            self.eat(Char::Comma.r#type());
+        }
+
+        self.eat(Keyword::Do.r#type());
+
+        let body = self.statement();
+        self.variables
+            .truncate(self.variables.len() - records.len());
+        self.control_variables = control_variables;
+
+        match body {
+            Ok(body) => Ok(Rc::new(With::new(records, body))),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// fields returns the declared types of the fields of the record selected by the designator,
+    /// there are none when its type can not be told while parsing.
+    fn fields(&self, record: &Var) -> HashMap<String, Rc<TypeSpec>> {
+        let mut fields = HashMap::new();
+        if let Some(ts) = self.type_of(record) {
+            if let TypeSpec::Record(vds) = ts.as_ref() {
+                for vd in vds {
+                    for id in vd.ids() {
+                        fields.insert(id.to_uppercase(), vd.type_spec());
+                    }
+                }
+            }
+        }
+        fields
+    }
+
+    /// type_of returns the declared type of the component selected by the designator, None when
+    /// it can not be told while parsing.
+    fn type_of(&self, var: &Var) -> Option<Rc<TypeSpec>> {
+        let name = match var.name() {
+            Ok(Some(name)) => name.to_uppercase(),
+            _ => return None,
+        };
+        let mut ts = match self.variables.iter().rev().find_map(|v| v.get(&name)) {
+            Some(ts) => self.declared_type(ts.clone()),
+            None => return None,
+        };
+
+        let mut index = 0; // The number of index types of the array already selected.
+        for selector in var.selectors() {
+            let next = match (ts.as_ref(), selector) {
+                (TypeSpec::Array(indexes, element), Selector::Index(_)) => {
+                    index += 1;
+                    if index < indexes.len() {
+                        continue;
+                    }
+                    element.clone()
+                }
+                (TypeSpec::Record(vds), Selector::Field(field)) => {
+                    match vds
+                        .iter()
+                        .find(|vd| vd.ids().iter().any(|id| id.eq_ignore_ascii_case(field)))
+                    {
+                        Some(vd) => vd.type_spec(),
+                        None => return None,
+                    }
+                }
+                (TypeSpec::Pointer(name), Selector::Deref) => {
+                    Rc::new(TypeSpec::Named(name.clone()))
+                }
+                _ => return None,
+            };
+            index = 0;
+            ts = self.declared_type(next);
+        }
+
+        Some(ts)
+    }
+
+    /// declared_type follows the names of the types declared in the enclosing blocks to the types
+    /// they stand for, a name that is not declared there is a predefined type.
+    fn declared_type(&self, ts: Rc<TypeSpec>) -> Rc<TypeSpec> {
+        let mut ts = ts;
+        /* A type can not name itself more often than there are declared types */
+        let count: usize = self.types.iter().map(|t| t.len()).sum();
+        for _ in 0..=count {
+            let name = match ts.as_ref() {
+                TypeSpec::Named(name) => name.to_uppercase(),
+                _ => break,
+            };
+            ts = match self.types.iter().rev().find_map(|t| t.get(&name)) {
+                Some(t) => t.clone(),
+                None => break,
+            };
+        }
+        ts
+    }
+
+    /// BNF:
+    /// loop_control_statement: BREAK | CONTINUE
+    fn loop_control_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        let signal = if self.is_word(Keyword::Break) {
+            Signal::Break
+        } else {
+            Signal::Continue
+        };
+        self.eat(ID);
+
+        if self.loops == 0 {
+            eprintln!("[parser] [loop_control_statement] BREAK and CONTINUE must be inside a loop");
+            return Err(Error::LoopRequired);
+        }
+
+        Ok(Rc::new(LoopControl::new(signal)))
+    }
+
+    /// BNF:
+    /// exit_statement: EXIT (LPAREN expr? RPAREN)?
+    ///
+    /// Only a function returns a value.
+    fn exit_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        self.eat(ID);
+
+        let value = match self.optional_argument() {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+        if value.is_some() && self.functions.last() != Some(&true) {
+            eprintln!("[parser] [exit_statement] only a function can return a value");
+            return Err(Error::InvalidSyntax);
+        }
+
+        Ok(Rc::new(Exit::new(value)))
+    }
+
+    /// BNF:
+    /// halt_statement: HALT (LPAREN expr? RPAREN)?
+    fn halt_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        self.eat(ID);
+
+        match self.optional_argument() {
+            Ok(code) => Ok(Rc::new(Halt::new(code))),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// optional_argument parses the argument in parentheses of EXIT and HALT, both the argument
+    /// and the parentheses may be omitted.
+    fn optional_argument(&mut self) -> Result<Option<Rc<dyn Node>>, Error> {
+        if !Char::LeftParen.equal_type(self.current_token.r#type()) {
+            return Ok(None);
+        }
+        self.eat(Char::LeftParen.r#type());
+
+        let mut argument = None;
+        if !Char::RightParen.equal_type(self.current_token.r#type()) {
+            match self.expr() {
+                Ok(n) => argument = Some(n),
+                Err(e) => return Err(e),
+            };
+        }
+
+        self.eat(Char::RightParen.r#type());
+        Ok(argument)
+    }
+
+    /// BNF:
+    /// if_statement: IF expr THEN statement (ELSE statement)?
+    ///
+    /// The optional ELSE always belongs to the nearest IF (dangling else).
+    fn if_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        self.eat(Keyword::If.r#type());
+
+        let condition = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        self.eat(Keyword::Then.r#type());
+
+        let then_branch = match self.statement() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        let mut else_branch: Option<Rc<dyn Node>> = None;
+        if Keyword::Else.equal_type(self.current_token.r#type()) {
+            self.eat(Keyword::Else.r#type());
+            match self.statement() {
+                Ok(n) => else_branch = Some(n),
+                Err(e) => return Err(e),
+            };
+        }
+
+        Ok(Rc::new(If::new(condition, then_branch, else_branch)))
+    }
+
+    /// BNF:
+    /// while_statement: WHILE expr DO statement
+    fn while_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        self.eat(Keyword::While.r#type());
+
+        let condition = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        self.eat(Keyword::Do.r#type());
+
+        self.loops += 1;
+        let body = self.statement();
+        self.loops -= 1;
+        let body = match body {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Rc::new(While::new(condition, body)))
+    }
+
+    /// BNF:
+    /// repeat_statement: REPEAT statement_list UNTIL expr
+    fn repeat_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        let mut children = Vec::<Rc<dyn Node>>::new();
+
+        self.eat(Keyword::Repeat.r#type());
+
+        self.loops += 1;
+        let nodes = self.statement_list();
+        self.loops -= 1;
+        match nodes {
+            Ok(nodes) => nodes.iter().for_each(|n| children.push(n.clone())),
+            Err(e) => return Err(e),
+        };
+
+        self.eat(Keyword::Until.r#type());
+
+        let condition = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Rc::new(Repeat::new(children, condition)))
+    }
+
+    /// BNF:
+    /// for_statement: FOR variable ASSIGN expr (TO | DOWNTO) expr DO statement
+    fn for_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        self.eat(Keyword::For.r#type());
+
+        let name = match self.variable().name() {
             Ok(name_op) => match name_op {
                 Some(name) => name,
                 None => {
-                    println!(
-                        "[parser] [procedure_call] variable's name not found, current token: {}",
+                    eprintln!(
+                        "[parser] [for_statement] variable's name not found, current token: {}",
                         self.current_token
                     );
                     return Err(Error::VarNotFound);
@@ -359,39 +1328,294 @@ impl Parser {
             Err(e) => return Err(e),
         };
 
-        let mut params: Vec<Rc<dyn Node>> = Vec::new();
+        if self.control_variables.contains(&name.to_uppercase()) {
+            eprintln!(
+                "[parser] [for_statement] '{}' is already the control variable of an enclosing FOR statement",
+                name
+            );
+            return Err(Error::ControlVariableAssigned);
+        }
 
-        self.eat(Char::LeftParen.r#type());
+        self.eat(Op::Assign.r#type());
 
-        if Char::RightParen.equal_type(self.current_token.r#type()) {
-            self.eat(Char::RightParen.r#type());
+        let start = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        let downto = Keyword::Downto.equal_type(self.current_token.r#type());
+        if downto {
+            self.eat(Keyword::Downto.r#type());
         } else {
-            match self.expr() {
-                Ok(p) => params.push(p),
+            self.eat(Keyword::To.r#type());
+        }
+
+        let end = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        self.eat(Keyword::Do.r#type());
+
+        /* The control variable must not be assigned inside the body */
+        self.control_variables.push(name.to_uppercase());
+        self.loops += 1;
+        let body = self.statement();
+        self.loops -= 1;
+        self.control_variables.pop();
+
+        match body {
+            Ok(body) => Ok(Rc::new(For::new(&name, start, end, downto, body))),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// BNF:
+    /// case_statement: CASE expr OF case_element (SEMI case_element)* SEMI? (ELSE statement_list)? END
+    fn case_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
+        self.eat(Keyword::Case.r#type());
+
+        let selector = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        self.eat(Keyword::Of.r#type());
+
+        let mut elements: Vec<CaseElement> = Vec::new();
+        while !Keyword::Else.equal_type(self.current_token.r#type())
+            && !Keyword::End.equal_type(self.current_token.r#type())
+        {
+            match self.case_element() {
+                Ok(ce) => elements.push(ce),
                 Err(e) => return Err(e),
             };
 
-            loop {
-                if !Char::Comma.equal_type(self.current_token.r#type()) {
-                    break;
-                }
-                self.eat(Char::Comma.r#type());
+            if !Char::Semi.equal_type(self.current_token.r#type()) {
+                break;
+            }
+            self.eat(Char::Semi.r#type());
+        }
+
+        let mut else_children: Option<Vec<Rc<dyn Node>>> = None;
+        if Keyword::Else.equal_type(self.current_token.r#type()) {
+            self.eat(Keyword::Else.r#type());
+            match self.statement_list() {
+                Ok(nodes) => else_children = Some(nodes),
+                Err(e) => return Err(e),
+            };
+        }
+
+        self.eat(Keyword::End.r#type());
+
+        Ok(Rc::new(Case::new(selector, elements, else_children)))
+    }
+
+    /// BNF:
+    /// case_element: case_label (COMMA case_label)* COLON statement
+    /// case_label: expr (RANGE expr)?
+    fn case_element(&mut self) -> Result<CaseElement, Error> {
+        let mut labels: Vec<CaseLabel> = Vec::new();
+
+        loop {
+            let low = match self.expr() {
+                Ok(n) => n,
+                Err(e) => return Err(e),
+            };
+
+            let mut high: Option<Rc<dyn Node>> = None;
+            if Op::Range.equal_type(self.current_token.r#type()) {
+                self.eat(Op::Range.r#type());
                 match self.expr() {
-                    Ok(p) => params.push(p),
+                    Ok(n) => high = Some(n),
                     Err(e) => return Err(e),
                 };
             }
 
-            self.eat(Char::RightParen.r#type());
+            labels.push(CaseLabel::new(low, high));
+
+            if !Char::Comma.equal_type(self.current_token.r#type()) {
+                break;
+            }
+            self.eat(Char::Comma.r#type());
+        }
+
+        self.eat(Char::Colon.r#type());
+
+        match self.statement() {
+            Ok(n) => Ok(CaseElement::new(labels, n)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// BNF:
+    /// procedure_call_statement: id (LPAREN (expr (COMMA expr)*)? RPAREN)?
+    /// function_call: id LPAREN (expr (COMMA expr)*)? RPAREN
+    ///
+    /// A function without parameters is called by its name in an expression, see Var.
+    fn procedure_call(&mut self) -> Result<Rc<dyn Node>, Error> {
+        let name = match self.variable().name() {
+            Ok(name_op) => match name_op {
+                Some(name) => name,
+                None => {
+                    eprintln!(
+                        "[parser] [procedure_call] variable's name not found, current token: {}",
+                        self.current_token
+                    );
+                    return Err(Error::VarNotFound);
+                }
+            },
+            Err(e) => return Err(e),
+        };
+
+        let mut params: Vec<Rc<dyn Node>> = Vec::new();
+
+        /* The arguments of the predefined Write and WriteLn may have a field width */
+        let write = !self.is_declared(&name)
+            && matches!(
+                Builtin::lookup(&name),
+                Some(Builtin::Write) | Some(Builtin::WriteLn)
+            );
+
+        /* A call without arguments may omit the parentheses, e.g. `WriteLn;` */
+        if Char::LeftParen.equal_type(self.current_token.r#type()) {
+            self.eat(Char::LeftParen.r#type());
+
+            if Char::RightParen.equal_type(self.current_token.r#type()) {
+                self.eat(Char::RightParen.r#type());
+            } else {
+                loop {
+                    let param = if write {
+                        self.write_parameter()
+                    } else {
+                        self.expr()
+                    };
+                    match param {
+                        Ok(p) => params.push(p),
+                        Err(e) => return Err(e),
+                    };
+
+                    if !Char::Comma.equal_type(self.current_token.r#type()) {
+                        break;
+                    }
+                    self.eat(Char::Comma.r#type());
+                }
+
+                self.eat(Char::RightParen.r#type());
+            }
+        }
+
+        if let Err(e) = self.check_arguments(&name, &params) {
+            return Err(e);
         }
 
         Ok(Rc::new(ProcedureCall::new(&name, params)))
     }
 
     /// BNF:
-    /// assignment_statement: id ASSIGN expr
+    /// write_parameter: expr (COLON expr (COLON expr)?)?
+    fn write_parameter(&mut self) -> Result<Rc<dyn Node>, Error> {
+        let value = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+        if !Char::Colon.equal_type(self.current_token.r#type()) {
+            return Ok(value);
+        }
+
+        self.eat(Char::Colon.r#type());
+        let width = match self.expr() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        let mut decimals = None;
+        if Char::Colon.equal_type(self.current_token.r#type()) {
+            self.eat(Char::Colon.r#type());
+            match self.expr() {
+                Ok(n) => decimals = Some(n),
+                Err(e) => return Err(e),
+            };
+        }
+
+        Ok(Rc::new(FieldWidth::new(value, width, decimals)))
+    }
+
+    /// is_declared reports whether a procedure named `name` is declared in the visible blocks, a
+    /// declared procedure hides the predefined one of the same name.
+    fn is_declared(&self, name: &str) -> bool {
+        self.routines
+            .iter()
+            .any(|r| r.contains_key(&name.to_uppercase()))
+    }
+
+    /// check_arguments checks the number of the arguments, that only variables are passed to the
+    /// VAR and OUT parameters of the procedure and that no control variable of an enclosing FOR
+    /// statement is passed to them, the arguments of a builtin are checked by the builtin and
+    /// procedures unknown to the parser are checked when they are called.
+    fn check_arguments(&self, name: &str, params: &[Rc<dyn Node>]) -> Result<(), Error> {
+        let modes: Vec<PassMode> = match self
+            .routines
+            .iter()
+            .rev()
+            .find_map(|r| r.get(&name.to_uppercase()))
+        {
+            Some(modes) if modes.len() != params.len() => {
+                eprintln!(
+                    "[parser] [check_arguments] '{}' takes {} arguments, but {} were given",
+                    name,
+                    modes.len(),
+                    params.len()
+                );
+                return Err(Error::ArgumentCountMismatch);
+            }
+            Some(modes) => modes.clone(),
+            None => match Builtin::lookup(name) {
+                Some(b) => match b.check(params) {
+                    Ok(_) => (0..params.len()).map(|i| b.mode(i)).collect(),
+                    Err(e) => return Err(e),
+                },
+                None => return Ok(()),
+            },
+        };
+
+        for (i, (mode, param)) in modes.iter().zip(params.iter()).enumerate() {
+            if *mode != PassMode::Var && *mode != PassMode::Out {
+                continue;
+            }
+
+            if !matches!(param.r#type(), NodeType::Var) {
+                eprintln!(
+                    "[parser] [check_arguments] argument {} of '{}' must be a variable",
+                    i + 1,
+                    name
+                );
+                return Err(Error::VariableRequired);
+            }
+
+            if let Ok(Some(var)) = param.name() {
+                if self.control_variables.contains(&var.to_uppercase()) {
+                    eprintln!(
+                        "[parser] [check_arguments] control variable '{}' can not be passed to argument {} of '{}' inside the FOR statement",
+                        var,
+                        i + 1,
+                        name
+                    );
+                    return Err(Error::ControlVariableAssigned);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// BNF:
+    /// assignment_statement: designator ASSIGN expr
     fn assginment_statement(&mut self) -> Result<Rc<dyn Node>, Error> {
-        let left = self.variable();
+        let left = match self.designator() {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
 
         self.eat(Op::Assign.r#type());
 
@@ -404,7 +1628,7 @@ impl Parser {
             Ok(name_op) => match name_op {
                 Some(name) => name,
                 None => {
-                    println!(
+                    eprintln!(
                         "[parser] [assginment_statement] variable's name not found, current token: {}",
                         self.current_token
                     );
@@ -414,7 +1638,15 @@ impl Parser {
             Err(e) => return Err(e),
         };
 
-        let result = Assign::new(&name, right);
+        if self.control_variables.contains(&name.to_uppercase()) {
+            eprintln!(
+                "[parser] [assginment_statement] control variable '{}' can not be assigned inside the FOR statement",
+                name
+            );
+            return Err(Error::ControlVariableAssigned);
+        }
+
+        let result = Assign::new(left, right);
 
         Ok(Rc::new(result))
     }
@@ -427,19 +1659,107 @@ impl Parser {
         Rc::new(result)
     }
 
+    /// BNF:
+    /// designator: ID (LBRACKET expr (COMMA expr)* RBRACKET | DOT ID | CARET)*
+    fn designator(&mut self) -> Result<Rc<Var>, Error> {
+        let name = self.current_token.value().to_string();
+        self.eat(ID);
+
+        let mut selectors = Vec::new();
+        loop {
+            if Char::Caret.equal_type(self.current_token.r#type()) {
+                self.eat(Char::Caret.r#type());
+                selectors.push(Selector::Deref);
+                continue;
+            }
+
+            if Char::Dot.equal_type(self.current_token.r#type()) {
+                self.eat(Char::Dot.r#type());
+                selectors.push(Selector::Field(self.current_token.value().to_string()));
+                self.eat(ID);
+                continue;
+            }
+
+            if !Char::LeftBracket.equal_type(self.current_token.r#type()) {
+                break;
+            }
+
+            self.eat(Char::LeftBracket.r#type());
+
+            match self.expr() {
+                Ok(n) => selectors.push(Selector::Index(n)),
+                Err(e) => return Err(e),
+            };
+
+            while Char::Comma.equal_type(self.current_token.r#type()) {
+                self.eat(Char::Comma.r#type());
+                match self.expr() {
+                    Ok(n) => selectors.push(Selector::Index(n)),
+                    Err(e) => return Err(e),
+                };
+            }
+
+            self.eat(Char::RightBracket.r#type());
+        }
+
+        Ok(Rc::new(Var::designator(&name, selectors)))
+    }
+
     /// An empty production
     fn empty(&mut self) -> Rc<dyn Node> {
         Rc::new(NoOp::new())
     }
 
     /// BNF:
-    /// factor: PLUS factor | MINUS factor | INTEGER | LPAREN expr RPAREN | variable | procedure_call
+    /// factor: PLUS factor
+    ///       | MINUS factor
+    ///       | NOT factor
+    ///       | INTEGER
+    ///       | REAL
+    ///       | TRUE
+    ///       | FALSE
+    ///       | NIL
+    ///       | CHAR
+    ///       | STRING
+    ///       | LPAREN expr RPAREN
+    ///       | set_constructor
+    ///       | AT designator
+    ///       | designator
+    ///       | function_call
     fn factor(&mut self) -> Result<Rc<dyn Node>, Error> {
-        if NumberType::Integer.equal_type(self.current_token.r#type()) {
+        if NumberType::Boolean.equal_type(self.current_token.r#type()) {
+            for b in [Boolean::True, Boolean::False] {
+                if b.equal_value(self.current_token.value()) {
+                    self.eat(b.r#type());
+                    return Ok(Rc::new(ast::boolean::Boolean::new(b.bool())));
+                }
+            }
+            eprintln!(
+                "[parser] [factor] current token '{}' is invalid, TRUE or FALSE is required",
+                self.current_token
+            );
+            Err(Error::InvalidSyntax)
+        } else if NumberType::Char.equal_type(self.current_token.r#type())
+            || NumberType::String.equal_type(self.current_token.r#type())
+        {
+            let r#type = match NumberType::to_number_type(self.current_token.r#type()) {
+                Ok(t) => t,
+                Err(e) => return Err(e),
+            };
+            let node = ast::char_string::CharString::new(r#type, self.current_token.value());
+            self.eat(r#type.r#type());
+            Ok(Rc::new(node))
+        } else if Op::Not.equal_type(self.current_token.r#type()) {
+            self.eat(Op::Not.r#type());
+            match self.factor() {
+                Ok(n) => Ok(Rc::new(UnaryOp::new(Op::Not.r#type(), n))),
+                Err(e) => Err(e),
+            }
+        } else if NumberType::Integer.equal_type(self.current_token.r#type()) {
             let val = match self.current_token.value().parse::<i32>() {
                 Ok(v) => v,
                 Err(e) => {
-                    println!(
+                    eprintln!(
                         "[parser] [factor] parse num '{}' failed, current token: {}, error: {}",
                         self.current_token.value(),
                         self.current_token,
@@ -454,7 +1774,7 @@ impl Parser {
             let val = match self.current_token.value().parse::<f32>() {
                 Ok(v) => v,
                 Err(e) => {
-                    println!(
+                    eprintln!(
                         "[parser] [factor] parse num '{}' failed, current token: {}, error: {}",
                         self.current_token.value(),
                         self.current_token,
@@ -473,6 +1793,17 @@ impl Parser {
             };
             self.eat(Char::RightParen.r#type());
             Ok(node)
+        } else if Char::LeftBracket.equal_type(self.current_token.r#type()) {
+            self.set_constructor()
+        } else if Keyword::Nil.equal_type(self.current_token.r#type()) {
+            self.eat(Keyword::Nil.r#type());
+            Ok(Rc::new(ast::nil::Nil::new()))
+        } else if Char::At.equal_type(self.current_token.r#type()) {
+            self.eat(Char::At.r#type());
+            match self.designator() {
+                Ok(v) => Ok(Rc::new(ast::address_of::AddressOf::new(v))),
+                Err(e) => Err(e),
+            }
         } else if Op::Add.equal_type(self.current_token.r#type())
             || Op::Sub.equal_type(self.current_token.r#type())
         {
@@ -487,14 +1818,21 @@ impl Parser {
                 };
                 return Ok(Rc::new(UnaryOp::new(op.r#type(), node)));
             }
-            println!(
+            eprintln!(
                 "[parser] [factor] current token '{}' is invalid, '+' or '-' operation is required",
                 self.current_token
             );
             Err(Error::InvalidSyntax)
         } else if self.current_token.r#type() == ID {
-            if !Char::LeftParen.equal_value(self.lexer.current_char()) {
-                Ok(self.variable())
+            let call = match self.lexer.peek_token() {
+                Ok(t) => Char::LeftParen.equal_type(t.r#type()),
+                Err(e) => return Err(e),
+            };
+            if !call {
+                match self.designator() {
+                    Ok(v) => Ok(v),
+                    Err(e) => Err(e),
+                }
             } else {
                 match self.procedure_call() {
                     Ok(n) => Ok(n),
@@ -502,7 +1840,7 @@ impl Parser {
                 }
             }
         } else {
-            println!(
+            eprintln!(
                 "[parser] [factor] current token '{}' is invalid",
                 self.current_token
             );
@@ -511,7 +1849,42 @@ impl Parser {
     }
 
     /// BNF:
-    /// term: factor ((MUL | DIV) factor)*
+    /// set_constructor: LBRACKET (set_element (COMMA set_element)*)? RBRACKET
+    /// set_element: expr (RANGE expr)?
+    fn set_constructor(&mut self) -> Result<Rc<dyn Node>, Error> {
+        let mut elements = Vec::new();
+
+        self.eat(Char::LeftBracket.r#type());
+
+        while !Char::RightBracket.equal_type(self.current_token.r#type()) {
+            if !elements.is_empty() {
+                self.eat(Char::Comma.r#type());
+            }
+
+            let low = match self.expr() {
+                Ok(n) => n,
+                Err(e) => return Err(e),
+            };
+
+            let mut high = None;
+            if Op::Range.equal_type(self.current_token.r#type()) {
+                self.eat(Op::Range.r#type());
+                match self.expr() {
+                    Ok(n) => high = Some(n),
+                    Err(e) => return Err(e),
+                };
+            }
+
+            elements.push(SetElement::new(low, high));
+        }
+
+        self.eat(Char::RightBracket.r#type());
+
+        Ok(Rc::new(SetConstructor::new(elements)))
+    }
+
+    /// BNF:
+    /// term: factor ((MUL | DIV | INTEGER_DIV | MOD | AND) factor)*
     fn term(&mut self) -> Result<Rc<dyn Node>, Error> {
         let node = match self.factor() {
             Ok(n) => n,
@@ -521,8 +1894,19 @@ impl Parser {
         let mut result = node;
         while Op::Mul.equal_type(self.current_token.r#type())
             || Op::Div.equal_type(self.current_token.r#type())
+            || Op::IntDiv.equal_type(self.current_token.r#type())
+            || Op::Mod.equal_type(self.current_token.r#type())
+            || Op::And.equal_type(self.current_token.r#type())
         {
-            for op in [Op::Mul, Op::Div] {
+            if Op::And.equal_type(self.current_token.r#type()) {
+                match self.logical_op(result.clone(), Op::And) {
+                    Ok(n) => result = n,
+                    Err(e) => return Err(e),
+                };
+                continue;
+            }
+
+            for op in [Op::Mul, Op::Div, Op::IntDiv, Op::Mod] {
                 if !op.equal_type(self.current_token.r#type()) {
                     continue;
                 }
@@ -541,9 +1925,58 @@ impl Parser {
         Ok(result)
     }
 
+    /// logical_op eats the AND or OR op and builds the node with the right operand.
+    fn logical_op(&mut self, left: Rc<dyn Node>, op: Op) -> Result<Rc<dyn Node>, Error> {
+        /* Whether to short-circuit is decided by the directive in effect at the op */
+        let complete = self.lexer.complete_boolean();
+        self.eat(op.r#type());
+
+        let right = if Op::And.equal_type(op.r#type()) {
+            self.factor()
+        } else {
+            self.term()
+        };
+
+        match right {
+            Ok(n) => Ok(Rc::new(LogicalOp::new(
+                left,
+                Token::new(op.r#type(), op.value()),
+                n,
+                complete,
+            ))),
+            Err(e) => Err(e),
+        }
+    }
+
     /// BNF:
-    /// expr: term ((PLUS | MINUS) term)*
+    /// expr: simple_expression ((EQ | NE | LT | LE | GT | GE | IN) simple_expression)?
     fn expr(&mut self) -> Result<Rc<dyn Node>, Error> {
+        let node = match self.simple_expression() {
+            Ok(n) => n,
+            Err(e) => return Err(e),
+        };
+
+        for op in [Op::Eq, Op::NotEq, Op::Lt, Op::Le, Op::Gt, Op::Ge, Op::In] {
+            if !op.equal_type(self.current_token.r#type()) {
+                continue;
+            }
+            self.eat(op.r#type());
+            return match self.simple_expression() {
+                Ok(n) => Ok(Rc::new(BinOp::new(
+                    node,
+                    Token::new(op.r#type(), op.value()),
+                    n,
+                ))),
+                Err(e) => Err(e),
+            };
+        }
+
+        Ok(node)
+    }
+
+    /// BNF:
+    /// simple_expression: term ((PLUS | MINUS | OR) term)*
+    fn simple_expression(&mut self) -> Result<Rc<dyn Node>, Error> {
         let node = match self.term() {
             Ok(n) => n,
             Err(e) => return Err(e),
@@ -553,7 +1986,16 @@ impl Parser {
 
         while Op::Add.equal_type(self.current_token.r#type())
             || Op::Sub.equal_type(self.current_token.r#type())
+            || Op::Or.equal_type(self.current_token.r#type())
         {
+            if Op::Or.equal_type(self.current_token.r#type()) {
+                match self.logical_op(result.clone(), Op::Or) {
+                    Ok(n) => result = n,
+                    Err(e) => return Err(e),
+                };
+                continue;
+            }
+
             for op in [Op::Add, Op::Sub] {
                 if !op.equal_type(self.current_token.r#type()) {
                     continue;
@@ -1,18 +1,28 @@
 #[cfg(test)]
 mod tests {
-    use crate::global_scope::Scope;
+    use crate::ast::{block::Block, Node};
+    use crate::console::Console;
+    use crate::data_type::DataType;
+    use crate::error::Error;
+    use crate::global_scope::{Identifier, Scope, Signal};
+    use crate::lexer::lexeme::number::NumberType;
     use crate::lexer::Lexer;
     use crate::parser::Parser;
     use std::env;
     use std::fs;
+    use std::io::{self, Write};
+
+    const DEFAULT_TEST_CODE: &str = "./test-code/code-4.pas";
+
     use std::{cell::RefCell, rc::Rc};
     #[test]
     fn test_parser() {
         let args: Vec<String> = env::args().collect();
-        if args.len() == 0 {
-            panic!("code file not found");
-        }
-        let filename = &args[args.len() - 1];
+        /* Fall back to the default test code when no file is given, e.g. `cargo test` */
+        let filename = match args.last() {
+            Some(f) if f.ends_with(".pas") => f,
+            _ => DEFAULT_TEST_CODE,
+        };
 
         let code =
             fs::read_to_string(filename).expect("Something went wrong when reading the file");
@@ -29,4 +39,1409 @@ mod tests {
             println!("[test] test failed, error: {}", e);
         }
     }
+
+    /// parse parses the code as a block.
+    fn parse(code: &str) -> Result<Block, Error> {
+        let mut parser = Parser::new(Lexer::new(code));
+        parser.current_token = parser.lexer.get_next_token().unwrap();
+        parser.block()
+    }
+
+    /// execute parses the code as a block and executes it in a fresh scope.
+    fn execute(code: &str) -> Result<Rc<RefCell<Scope>>, Error> {
+        let block = match parse(code) {
+            Ok(b) => b,
+            Err(e) => return Err(e),
+        };
+
+        let scope = Rc::new(RefCell::new(Scope::new("test", None, 0)));
+        match block.visit(scope.clone()) {
+            Ok(_) => Ok(scope),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// run executes the code and panics if it fails.
+    fn run(code: &str) -> Rc<RefCell<Scope>> {
+        match execute(code) {
+            Ok(scope) => scope,
+            Err(e) => panic!("run failed, error: {}", e),
+        }
+    }
+
+    /// value_of returns the value of the variable `name` in the scope.
+    fn value_of(scope: &Rc<RefCell<Scope>>, name: &str) -> String {
+        match scope.borrow().get(name) {
+            Some(Identifier::Variable(vs)) => match vs.value() {
+                Some(v) => v.content(),
+                None => String::new(),
+            },
+            _ => panic!("variable '{}' not found", name),
+        }
+    }
+
+    #[test]
+    fn test_if_statement() {
+        let scope = run("
+            Var a, b, c, d : integer;
+            Begin
+              a := 1;
+              if a = 1 then b := 10 else b := 20;
+              if a <> 1 then c := 10 else c := 20;
+              if a >= 1 then if a > 1 then d := 1 else d := 2
+            End");
+
+        assert_eq!(value_of(&scope, "b"), "10");
+        assert_eq!(value_of(&scope, "c"), "20");
+        /* The ELSE binds to the inner IF */
+        assert_eq!(value_of(&scope, "d"), "2");
+    }
+
+    #[test]
+    fn test_while_statement() {
+        let scope = run("
+            Var i, sum, n : integer;
+            Begin
+              i := 1;
+              sum := 0;
+              while i <= 10 do
+              begin
+                sum := sum + i;
+                i := i + 1
+              end;
+              n := 0;
+              while n > 0 do n := n - 1
+            End");
+
+        assert_eq!(value_of(&scope, "sum"), "55");
+        assert_eq!(value_of(&scope, "i"), "11");
+        assert_eq!(value_of(&scope, "n"), "0");
+    }
+
+    #[test]
+    fn test_repeat_statement() {
+        let scope = run("
+            Var i, fact, n : integer;
+            Begin
+              i := 0;
+              fact := 1;
+              repeat
+                i := i + 1;
+                fact := fact * i
+              until i = 5;
+              n := 10;
+              repeat n := n + 1 until n > 0
+            End");
+
+        assert_eq!(value_of(&scope, "fact"), "120");
+        /* The body runs once even though the condition already holds */
+        assert_eq!(value_of(&scope, "n"), "11");
+    }
+
+    #[test]
+    fn test_for_statement() {
+        let scope = run("
+            Var i, j, sum, count, last : integer;
+            Begin
+              sum := 0;
+              for i := 1 to 10 do sum := sum + i;
+              count := 0;
+              for j := 3 downto 1 do
+              begin
+                count := count + 1;
+                last := j
+              end
+            End");
+
+        assert_eq!(value_of(&scope, "sum"), "55");
+        assert_eq!(value_of(&scope, "count"), "3");
+        assert_eq!(value_of(&scope, "last"), "1");
+    }
+
+    #[test]
+    fn test_for_statement_assign_control_variable() {
+        let code = "
+            Var i : integer;
+            Begin
+              for i := 1 to 10 do i := i + 1
+            End";
+
+        assert!(matches!(parse(code), Err(Error::ControlVariableAssigned)));
+
+        /* Nor can it be changed through a VAR or OUT parameter */
+        for body in ["Inc(i)", "Read(i)", "Bump(i)", "Reset(i)"] {
+            let code = format!(
+                "
+                Var i : integer;
+                Procedure Bump(Var n : integer);
+                Begin n := n + 1 End;
+                Procedure Reset(Out n : integer);
+                Begin n := 0 End;
+                Begin
+                  for i := 1 to 10 do {}
+                End",
+                body
+            );
+
+            assert!(matches!(parse(&code), Err(Error::ControlVariableAssigned)));
+        }
+
+        /* A field of a WITH record named like the control variable is not the control variable */
+        let scope = run("
+            Type Point = Record i, j : integer End;
+            Var i, n : integer;
+                r : Point;
+                a : array [1..2] of Point;
+            Begin
+              n := 0;
+              for i := 1 to 2 do
+                with a[i], r do
+                begin
+                  i := 10;
+                  Inc(i);
+                  n := n + i
+                end
+            End");
+
+        assert_eq!(value_of(&scope, "n"), "22");
+
+        let code = "
+            Type Point = Record x, y : integer End;
+            Var i : integer;
+                r : Point;
+            Begin
+              for i := 1 to 10 do with r do i := 0
+            End";
+
+        assert!(matches!(parse(code), Err(Error::ControlVariableAssigned)));
+    }
+
+    #[test]
+    fn test_case_statement() {
+        let scope = run("
+            Var i, a, b, c : integer;
+            Begin
+              i := 4;
+              case i of
+                1, 2: a := 1;
+                3..5: a := 2;
+              else
+                a := 3
+              end;
+              case i * 2 of
+                1..7: b := 1;
+                8: b := 2
+              end;
+              case i of
+                -1, 0: c := 1
+              else c := 2; c := c * 10
+              end
+            End");
+
+        assert_eq!(value_of(&scope, "a"), "2");
+        assert_eq!(value_of(&scope, "b"), "2");
+        assert_eq!(value_of(&scope, "c"), "20");
+    }
+
+    #[test]
+    fn test_case_statement_no_label_matched() {
+        let code = "
+            Var i, a : integer;
+            Begin
+              i := 9;
+              case i of
+                1: a := 1
+              end
+            End";
+
+        assert!(matches!(execute(code), Err(Error::CaseLabelNotFound)));
+    }
+
+    #[test]
+    fn test_boolean() {
+        let scope = run("
+            Var a, b, c, d : boolean;
+                x : integer;
+            Begin
+              x := 5;
+              a := true;
+              b := not a or (x > 3) and (x < 10);
+              c := False and (undefined > 1);
+              d := (a = b) and not (x <> 5)
+            End");
+
+        assert_eq!(value_of(&scope, "a"), "true");
+        assert_eq!(value_of(&scope, "b"), "true");
+        /* The right operand is not evaluated */
+        assert_eq!(value_of(&scope, "c"), "false");
+        assert_eq!(value_of(&scope, "d"), "true");
+    }
+
+    #[test]
+    fn test_boolean_complete_evaluation() {
+        let code = "
+            Var c : boolean;
+            Begin
+              {$B+}
+              c := false and (undefined > 1)
+            End";
+
+        assert!(matches!(execute(code), Err(Error::VarNotFound)));
+    }
+
+    #[test]
+    fn test_division() {
+        let scope = run("
+            Var a, b, c, d : integer;
+                x, y : real;
+            Begin
+              x := 7 / 2;
+              y := 8 / 4;
+              a := 7 div 2;
+              b := -7 div 2;
+              c := -7 mod 3;
+              d := 7 mod -3
+            End");
+
+        assert_eq!(value_of(&scope, "x"), "3.5");
+        assert_eq!(value_of(&scope, "a"), "3");
+        assert_eq!(value_of(&scope, "b"), "-3");
+        assert_eq!(value_of(&scope, "c"), "-1");
+        assert_eq!(value_of(&scope, "d"), "1");
+        assert!(matches!(
+            scope.borrow().get("y"),
+            Some(Identifier::Variable(vs)) if vs.r#type() == DataType::Simple(NumberType::Real)
+        ));
+
+        assert!(matches!(
+            execute("Var a : integer; Begin a := 1 div 0 End"),
+            Err(Error::DivisionByZero)
+        ));
+        assert!(matches!(
+            execute("Var a : integer; Begin a := 5 mod 2.0 End"),
+            Err(Error::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_function() {
+        let scope = run("
+            Var a, b : integer;
+                x : real;
+
+            Function Fact(n : integer) : integer;
+            Begin
+              if n <= 1 then Fact := 1 else Fact := n * Fact(n - 1)
+            End;
+
+            Function Half(n : integer) : real;
+            Begin
+              Result := n / 2
+            End;
+
+            Function Seven : integer;
+            Begin
+              Seven := 7
+            End;
+
+            Begin
+              a := Fact(5) + 1;
+              x := Half(3);
+              b := Seven * 2
+            End");
+
+        assert_eq!(value_of(&scope, "a"), "121");
+        assert_eq!(value_of(&scope, "x"), "1.5");
+        assert_eq!(value_of(&scope, "b"), "14");
+
+        assert!(matches!(
+            execute(
+                "
+                Var a : integer;
+                Function F : integer;
+                Begin
+                End;
+                Begin
+                  a := F()
+                End"
+            ),
+            Err(Error::ResultNotAssigned)
+        ));
+    }
+
+    #[test]
+    fn test_function_declaring_result() {
+        let scope = run("
+            Var a, b : integer;
+
+            Function Double(n : integer) : integer;
+            Var result : integer;
+            Begin
+              result := n * 2;
+              Double := result
+            End;
+
+            Function Triple(Result : integer) : integer;
+            Begin
+              Triple := Result * 3
+            End;
+
+            Begin
+              a := Double(4);
+              b := Triple(5)
+            End");
+
+        assert_eq!(value_of(&scope, "a"), "8");
+        assert_eq!(value_of(&scope, "b"), "15");
+    }
+
+    #[test]
+    fn test_parameter_modes() {
+        let scope = run("
+            Var a, b, c, d : integer;
+
+            Procedure Swap(Var x, y : integer);
+            Var t : integer;
+            Begin
+              t := x;
+              x := y;
+              y := t
+            End;
+
+            Procedure SwapTwice(Var x, y : integer);
+            Begin
+              Swap(x, y);
+              Swap(x, y);
+              Swap(x, y)
+            End;
+
+            Procedure Double(Const x : integer; Out y : integer);
+            Begin
+              y := x * 2
+            End;
+
+            Begin
+              a := 1;
+              b := 2;
+              SwapTwice(a, b);
+              Double(a + 1, c);
+              d := 5;
+              Double(d, d)
+            End");
+
+        assert_eq!(value_of(&scope, "a"), "2");
+        assert_eq!(value_of(&scope, "b"), "1");
+        assert_eq!(value_of(&scope, "c"), "6");
+        assert_eq!(value_of(&scope, "d"), "10");
+
+        assert!(matches!(
+            execute(
+                "
+                Procedure P(Const x : integer);
+                Begin
+                  x := 1
+                End;
+                Begin
+                  P(1)
+                End"
+            ),
+            Err(Error::ConstantAssigned)
+        ));
+        assert!(matches!(
+            parse(
+                "
+                Var a : integer;
+                Procedure P(Var x : integer);
+                Begin
+                End;
+                Begin
+                  P(a + 1)
+                End"
+            ),
+            Err(Error::VariableRequired)
+        ));
+
+        /* OUT is not reserved, it may name a parameter or a variable */
+        let scope = run("
+            Var out, doubled : integer;
+            Procedure Twice(out : integer; Out result : integer);
+            Begin
+              result := out * 2
+            End;
+            Procedure Copy(x : integer; Out out : integer);
+            Begin
+              out := x
+            End;
+            Begin
+              Copy(3, out);
+              Twice(out, doubled)
+            End");
+
+        assert_eq!(value_of(&scope, "out"), "3");
+        assert_eq!(value_of(&scope, "doubled"), "6");
+
+        /* The number of the arguments must match the number of the parameters */
+        for call in ["P(1)", "P(1, 2, 3)", "P()"] {
+            let code = format!(
+                "
+                Procedure P(x, y : integer);
+                Begin
+                End;
+                Begin
+                  {}
+                End",
+                call
+            );
+
+            assert!(matches!(parse(&code), Err(Error::ArgumentCountMismatch)));
+        }
+    }
+
+    #[test]
+    fn test_constant_declaration() {
+        let scope = run("
+            Const Pi = 3.14159;
+                  Max = 10 * 4;
+                  Half = Max div 2;
+                  Debug = false;
+            Var a : integer;
+                r : real;
+            Begin
+              a := Half + 1;
+              r := Pi * 2
+            End");
+
+        assert_eq!(value_of(&scope, "a"), "21");
+        assert_eq!(value_of(&scope, "r"), "6.28318");
+        assert!(matches!(
+            scope.borrow().get("Max"),
+            Some(Identifier::Constant(cs)) if cs.value().value() == "40"
+        ));
+
+        assert!(matches!(
+            execute(
+                "
+                Const Max = 10;
+                Begin
+                  Max := 11
+                End"
+            ),
+            Err(Error::ConstantAssigned)
+        ));
+    }
+
+    #[test]
+    fn test_type_declaration() {
+        let scope = run("
+            Type MyInt = integer;
+                 Whole = MyInt;
+                 Float = REAL;
+                 Flag = BOOLEAN;
+            Var a : whole;
+                r : FLOAT;
+                f : Flag;
+            Begin
+              A := 7;
+              r := a / 2;
+              F := r > 3
+            End");
+
+        assert_eq!(value_of(&scope, "a"), "7");
+        assert_eq!(value_of(&scope, "R"), "3.5");
+        assert_eq!(value_of(&scope, "f"), "true");
+        assert!(matches!(
+            scope.borrow().get("myint"),
+            Some(Identifier::Type(_))
+        ));
+
+        assert!(matches!(
+            execute(
+                "
+                Var a : Unknown;
+                Begin
+                  a := 1
+                End"
+            ),
+            Err(Error::TypeNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_array() {
+        let scope = run("
+            Const N = 3;
+            Type Vector = array[1..N] of integer;
+            Var v, w : Vector;
+                m : array[1..2, 0..1] of real;
+                flags : array[boolean] of integer;
+                i, sum, first, count : integer;
+                corner : real;
+            Procedure Bump(Var a : Vector; k : integer);
+            Begin
+              a[k] := a[k] + 100
+            End;
+            Begin
+              for i := 1 to N do v[i] := i * i;
+              w := v;
+              w[1] := 42;
+              Bump(v, 2);
+              sum := 0;
+              for i := 1 to N do sum := sum + v[i];
+              first := w[1] + v[1];
+              m[2, 1] := 1;
+              m[2][1] := m[2, 1] + 0.5;
+              corner := m[2][1];
+              flags[1 > 0] := 7;
+              count := flags[true] + flags[false]
+            End");
+
+        assert_eq!(value_of(&scope, "sum"), "114");
+        assert_eq!(value_of(&scope, "first"), "43");
+        assert_eq!(value_of(&scope, "corner"), "1.5");
+        assert_eq!(value_of(&scope, "count"), "7");
+        assert_eq!(value_of(&scope, "v"), "[1, 104, 9]");
+
+        assert!(matches!(
+            execute(
+                "
+                Var a : array[1..3] of integer;
+                Begin
+                  a[4] := 1
+                End"
+            ),
+            Err(Error::IndexOutOfRange)
+        ));
+
+        assert!(matches!(
+            execute(
+                "
+                Var a : array[1..3] of integer;
+                    b : array[0..2] of integer;
+                Begin
+                  a := b
+                End"
+            ),
+            Err(Error::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_record() {
+        let scope = run("
+            Type Point = record x, y : integer end;
+                 Segment = record
+                   from, upto : Point;
+                   visible : boolean;
+                 end;
+            Var s, t : Segment;
+                path : array[1..3] of Point;
+                i, len, startx, moved : integer;
+            Procedure Shift(Var n : integer; d : integer);
+            Begin
+              n := n + d
+            End;
+            Function Origin : Point;
+            Begin
+              Origin.x := 0;
+              Origin.y := 0
+            End;
+            Begin
+              s.from.x := 1;
+              s.from.y := 2;
+              s.upto := s.from;
+              s.upto.x := 10;
+              s.visible := true;
+              t := s;
+              t.from.x := 5;
+              startx := s.from.x;
+              len := t.upto.x - t.from.x;
+              for i := 1 to 3 do path[i].x := i;
+              path[2] := Origin;
+              i := 3;
+              Shift(path[i].x, 4);
+              Shift(s.upto.y, 1);
+              moved := path[1].x + path[2].x + path[3].x + s.upto.y
+            End");
+
+        assert_eq!(value_of(&scope, "startx"), "1");
+        assert_eq!(value_of(&scope, "len"), "5");
+        assert_eq!(value_of(&scope, "moved"), "11");
+        assert_eq!(value_of(&scope, "t"), "[[5, 2], [10, 2], true]");
+
+        assert!(matches!(
+            execute(
+                "
+                Var p : record x : integer end;
+                Begin
+                  p.z := 1
+                End"
+            ),
+            Err(Error::FieldNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_char_string() {
+        let scope = run("
+            Var s, t : string;
+                short : string[5];
+                c, last : char;
+                counts : array['a'..'c'] of integer;
+                n, kind : integer;
+                less, same : boolean;
+            Begin
+              s := 'It''s';
+              c := 'x';
+              t := s + ' ' + c + #33;
+              short := 'Hello, world';
+              less := 'abc' < 'abd';
+              same := (c = 'x') and (s <> t);
+              n := 0;
+              for c := 'a' to 'c' do
+              begin
+                n := n + 1;
+                counts[c] := n;
+                last := c
+              end;
+              case last of
+                'a'..'b': kind := 1;
+                'c': kind := 2
+              end;
+              n := counts['b'] + kind
+            End");
+
+        assert_eq!(value_of(&scope, "t"), "It's x!");
+        assert_eq!(value_of(&scope, "short"), "Hello");
+        assert_eq!(value_of(&scope, "less"), "true");
+        assert_eq!(value_of(&scope, "same"), "true");
+        assert_eq!(value_of(&scope, "n"), "4");
+
+        assert!(matches!(
+            execute("Var c : char; Begin c := 'ab' End"),
+            Err(Error::TypeMismatch)
+        ));
+        assert!(matches!(
+            execute("Var i : integer; Begin i := 'a' + 1 End"),
+            Err(Error::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_enum_subrange() {
+        let scope = run("
+            Type Color = (Red, Green, Blue);
+                 Digit = 0..9;
+            Var c, last : Color;
+                warm : Red..Green;
+                d : Digit;
+                counts : array[Color] of integer;
+                squares : array[Digit] of integer;
+                n, kind : integer;
+                before : boolean;
+            Begin
+              n := 0;
+              for c := Red to Blue do
+              begin
+                n := n + 1;
+                counts[c] := n;
+                last := c
+              end;
+              case last of
+                Red, Green: kind := 1;
+                Blue: kind := 2
+              end;
+              warm := Green;
+              before := warm < last;
+              for d := 0 to 9 do
+                squares[d] := d * d;
+              d := 7;
+              n := counts[Green] + squares[d] + kind
+            End");
+
+        assert_eq!(value_of(&scope, "last"), "BLUE");
+        assert_eq!(value_of(&scope, "warm"), "GREEN");
+        assert_eq!(value_of(&scope, "before"), "true");
+        assert_eq!(value_of(&scope, "n"), "53");
+
+        let scope = run("
+            Type Color = (Red, Green, Blue);
+            Const Favorite = Green;
+            Var c : Color;
+                n : integer;
+            Begin
+              c := Favorite;
+              n := Ord(Favorite)
+            End");
+
+        assert_eq!(value_of(&scope, "c"), "GREEN");
+        assert_eq!(value_of(&scope, "n"), "1");
+
+        assert!(matches!(
+            execute("Type Digit = 0..9; Var d : Digit; Begin d := 10 End"),
+            Err(Error::OutOfRange)
+        ));
+        assert!(matches!(
+            execute("Type Color = (Red, Green); Var n : integer; Begin n := Red End"),
+            Err(Error::TypeMismatch)
+        ));
+        assert!(matches!(
+            execute("Var a : array[integer] of integer; Begin End"),
+            Err(Error::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_set() {
+        let scope = run("
+            Type Color = (Red, Green, Blue);
+            Var letters, vowels, consonants : set of char;
+                small, evens, both, none : set of 0..9;
+                colors : set of Color;
+                c : char;
+                n, i : integer;
+                subset, member, empty : boolean;
+            Begin
+              letters := ['a'..'z', 'A'..'Z'];
+              vowels := ['a', 'e', 'i', 'o', 'u'];
+              consonants := letters - vowels - ['A'..'Z'];
+              n := 0;
+              for c := 'a' to 'z' do
+                if c in consonants then
+                  n := n + 1;
+              small := [0..4];
+              evens := [];
+              for i := 0 to 4 do
+                evens := evens + [i * 2];
+              both := small * evens;
+              none := [];
+              subset := (both <= small) and (evens >= both) and not (small <= evens);
+              colors := [Red, Blue];
+              member := (Green in colors) or not (Blue in colors);
+              empty := (none = []) and (small <> evens) and not (3 in none)
+            End");
+
+        assert_eq!(value_of(&scope, "n"), "21");
+        assert_eq!(value_of(&scope, "vowels"), "[a, e, i, o, u]");
+        assert_eq!(value_of(&scope, "both"), "[0, 2, 4]");
+        assert_eq!(value_of(&scope, "none"), "[]");
+        assert_eq!(value_of(&scope, "colors"), "[RED, BLUE]");
+        assert_eq!(value_of(&scope, "subset"), "true");
+        assert_eq!(value_of(&scope, "member"), "false");
+        assert_eq!(value_of(&scope, "empty"), "true");
+
+        assert!(matches!(
+            execute("Var s : set of 0..9; Begin s := [1, 10] End"),
+            Err(Error::OutOfRange)
+        ));
+        assert!(matches!(
+            execute("Var s : set of char; Begin s := [1, 2] End"),
+            Err(Error::TypeMismatch)
+        ));
+        assert!(matches!(
+            execute("Var s : set of integer; Begin End"),
+            Err(Error::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_pointer() {
+        let scope = run("
+            Type PNode = ^Node;
+                 Node = record
+                   value : integer;
+                   next : PNode
+                 end;
+            Var head, p : PNode;
+                q : ^integer;
+                i, sum, count, x : integer;
+                same : boolean;
+            Begin
+              head := nil;
+              for i := 1 to 3 do
+              begin
+                New(p);
+                p^.value := i * 10;
+                p^.next := head;
+                head := p
+              end;
+              sum := 0;
+              count := 0;
+              p := head;
+              while p <> nil do
+              begin
+                sum := sum + p^.value;
+                count := count + 1;
+                p := p^.next
+              end;
+              head^.next^.value := 5;
+              i := head^.next^.value;
+              x := 1;
+              q := @x;
+              q^ := q^ + 41;
+              New(p);
+              same := p = p;
+              Dispose(p)
+            End");
+
+        assert_eq!(value_of(&scope, "sum"), "60");
+        assert_eq!(value_of(&scope, "count"), "3");
+        assert_eq!(value_of(&scope, "i"), "5");
+        assert_eq!(value_of(&scope, "x"), "42");
+        assert_eq!(value_of(&scope, "same"), "true");
+
+        assert!(matches!(
+            execute("Type PInt = ^integer; Var p : PInt; i : integer; Begin p := nil; i := p^ End"),
+            Err(Error::NilPointer)
+        ));
+        assert!(matches!(
+            execute("Type PInt = ^integer; Var p : PInt; Begin New(p); Dispose(p); p^ := 1 End"),
+            Err(Error::InvalidPointer)
+        ));
+        assert!(matches!(
+            execute(
+                "Var p : ^integer; q : ^char; b : boolean; Begin New(p); New(q); b := p = q End"
+            ),
+            Err(Error::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_lexical_scope() {
+        let scope = run("
+            Var x, seen, total : integer;
+
+            Procedure Show;
+            Begin
+              seen := x
+            End;
+
+            Procedure Caller;
+            Var x : integer;
+            Begin
+              x := 99;
+              Show()
+            End;
+
+            Procedure Outer(a : integer);
+            Var y : integer;
+
+              Procedure Inner;
+              Begin
+                y := y + a;
+                x := x + y
+              End;
+
+            Begin
+              y := 1;
+              Inner();
+              Inner()
+            End;
+
+            Function Sum(n : integer) : integer;
+
+              Procedure Add(k : integer);
+              Begin
+                Sum := Result + k
+              End;
+
+            Begin
+              Sum := 0;
+              while n > 0 do
+              begin
+                Add(n);
+                n := n - 1
+              end
+            End;
+
+            Begin
+              x := 5;
+              Caller();
+              Outer(2);
+              total := Sum(4)
+            End");
+
+        assert_eq!(value_of(&scope, "seen"), "5");
+        assert_eq!(value_of(&scope, "x"), "13");
+        assert_eq!(value_of(&scope, "total"), "10");
+    }
+
+    #[test]
+    fn test_forward() {
+        let scope = run("
+            Var even, odd : boolean;
+                f : integer;
+
+            Function IsOdd(n : integer) : boolean; forward;
+            Procedure Count(var total : integer; n : integer); forward;
+
+            Function IsEven(n : integer) : boolean;
+            Begin
+              if n = 0 then IsEven := true else IsEven := IsOdd(n - 1)
+            End;
+
+            Function IsOdd;
+            Begin
+              if n = 0 then IsOdd := false else IsOdd := IsEven(n - 1)
+            End;
+
+            Procedure Count(var total : integer; n : integer);
+            Var local : integer;
+            Begin
+              local := n;
+              if n > 0 then Count(total, n - 1);
+              total := total + local
+            End;
+
+            Begin
+              even := IsEven(10);
+              odd := IsOdd(7);
+              f := 0;
+              Count(f, 4)
+            End");
+
+        assert_eq!(value_of(&scope, "even"), "true");
+        assert_eq!(value_of(&scope, "odd"), "true");
+        assert_eq!(value_of(&scope, "f"), "10");
+
+        assert!(matches!(
+            parse(
+                "
+                Procedure P(a : integer); forward;
+                Procedure P(a : real); Begin End;
+                Begin End."
+            ),
+            Err(Error::ForwardMismatch)
+        ));
+        assert!(matches!(
+            parse("Procedure P; forward; Begin End."),
+            Err(Error::ProcedureNotFound)
+        ));
+
+        /* FORWARD is a directive, not a reserved word */
+        let scope = run("
+            Var forward : integer;
+            Procedure Step; forward;
+            Procedure Step;
+            Begin
+              forward := forward + 1
+            End;
+            Begin
+              forward := 1;
+              Step()
+            End");
+
+        assert_eq!(value_of(&scope, "forward"), "2");
+    }
+
+    /// Output is a writer whose text is kept after the console is dropped.
+    struct Output(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_standard_io() {
+        let block = match parse(
+            "
+            Var i, j : integer;
+                r : real;
+                c : char;
+                s : string;
+            Procedure Separate;
+            Begin
+              Write('|')
+            End;
+            Begin
+              Read(i, j);
+              ReadLn;
+              ReadLn(r);
+              ReadLn(s);
+              Read(c);
+              Write('sum=', i + j, ' ');
+              WriteLn(true, ' ', c);
+              WriteLn(r, '|', -r:12, '|', r:8:2, '|', i:3, '|', s:7, '|');
+              Separate;
+              WriteLn;
+              WriteLn()
+            End",
+        ) {
+            Ok(b) => b,
+            Err(e) => panic!("parse failed, error: {}", e),
+        };
+
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let input = "3 4 skipped\n  2.5 ignored\nhello\nZ\n";
+        let scope = Rc::new(RefCell::new(Scope::new("test", None, 0)));
+        scope.borrow_mut().set_console(Console::new(
+            Box::new(io::Cursor::new(input.as_bytes())),
+            Box::new(Output(output.clone())),
+        ));
+        if let Err(e) = block.visit(scope.clone()) {
+            panic!("run failed, error: {}", e);
+        }
+
+        assert_eq!(value_of(&scope, "s"), "hello");
+        assert_eq!(
+            String::from_utf8(output.borrow().clone()).unwrap(),
+            "sum=7 TRUE Z\n 2.5000000000E+00|-2.50000E+00|    2.50|  3|  hello|\n|\n\n"
+        );
+
+        assert!(matches!(
+            parse("Var x : integer; Begin ReadLn(x + 1) End."),
+            Err(Error::VariableRequired)
+        ));
+    }
+
+    #[test]
+    fn test_ordinal_builtins() {
+        let scope = run("
+            Type Color = (Red, Green, Blue);
+                 Small = 1..5;
+            Var c, top : Color;
+                n, code, first, last : integer;
+                s : Small;
+                a : array[Small] of integer;
+                ch, next : char;
+                isOdd : boolean;
+            Begin
+              c := Red;
+              n := 10;
+              s := 4;
+              ch := 'a';
+              Inc(n);
+              Inc(n, 5);
+              Dec(n, 2);
+              Inc(c);
+              Inc(s);
+              Inc(ch, 2);
+              code := Ord('A') + Ord(Blue);
+              next := Chr(Ord(ch) + 1);
+              top := Succ(c);
+              isOdd := Odd(Pred(8));
+              first := Low(a);
+              last := High(Small);
+              a[High(a)] := 7;
+              Inc(a[5], 3)
+            End");
+
+        assert_eq!(value_of(&scope, "n"), "14");
+        assert_eq!(value_of(&scope, "c"), "GREEN");
+        assert_eq!(value_of(&scope, "s"), "5");
+        assert_eq!(value_of(&scope, "ch"), "c");
+        assert_eq!(value_of(&scope, "code"), "67");
+        assert_eq!(value_of(&scope, "next"), "d");
+        assert_eq!(value_of(&scope, "top"), "BLUE");
+        assert_eq!(value_of(&scope, "isOdd"), "true");
+        assert_eq!(value_of(&scope, "first"), "1");
+        assert_eq!(value_of(&scope, "last"), "5");
+        assert_eq!(value_of(&scope, "a"), "[0, 0, 0, 0, 10]");
+
+        assert!(matches!(
+            execute("Type Small = 1..5; Var s : Small; Begin s := 5; Inc(s) End."),
+            Err(Error::OutOfRange)
+        ));
+        assert!(matches!(
+            execute("Type Color = (Red, Green); Var c : Color; Begin c := Pred(Red) End."),
+            Err(Error::OutOfRange)
+        ));
+        assert!(matches!(
+            parse("Begin Inc(5) End."),
+            Err(Error::VariableRequired)
+        ));
+        assert!(matches!(
+            parse("Var c : char; Begin c := Chr(1, 2) End."),
+            Err(Error::ArgumentCountMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_math_builtins() {
+        let scope = run("
+            Var i, absolute, square, truncated, rounded : integer;
+                x, root, fraction, whole, angle : real;
+            Begin
+              i := -7;
+              x := -2.5;
+              absolute := Abs(i);
+              square := Sqr(i);
+              root := Sqrt(16);
+              truncated := Trunc(x);
+              rounded := Round(x);
+              fraction := Frac(x);
+              whole := Int(x);
+              angle := Sin(0) + Cos(0) + Ln(Exp(0))
+            End");
+
+        assert_eq!(value_of(&scope, "absolute"), "7");
+        assert_eq!(value_of(&scope, "square"), "49");
+        assert_eq!(value_of(&scope, "root"), "4");
+        assert_eq!(value_of(&scope, "truncated"), "-2");
+        assert_eq!(value_of(&scope, "rounded"), "-3");
+        assert_eq!(value_of(&scope, "fraction"), "-0.5");
+        assert_eq!(value_of(&scope, "whole"), "-2");
+        assert_eq!(value_of(&scope, "angle"), "1");
+
+        /* Sqrt always gives a REAL, Abs keeps the type of its argument */
+        assert!(matches!(
+            execute("Var i : integer; Begin i := Sqrt(4) End."),
+            Err(Error::TypeMismatch)
+        ));
+        assert!(matches!(
+            execute("Var i : integer; Begin i := Abs(-2.0) End."),
+            Err(Error::TypeMismatch)
+        ));
+        assert!(matches!(
+            execute("Var x : real; Begin x := Sqrt(-1) End."),
+            Err(Error::OutOfDomain)
+        ));
+        assert!(matches!(
+            execute("Var x : real; Begin x := Ln(0) End."),
+            Err(Error::OutOfDomain)
+        ));
+    }
+
+    #[test]
+    fn test_goto() {
+        let scope = run("
+            Label 10, 20, 30;
+            Var i, j, k, total : integer;
+            Begin
+              i := 0;
+              total := 0;
+            10:
+              i := i + 1;
+              if i < 5 then goto 10;
+              for j := 1 to 10 do
+                begin
+                  k := j;
+                  repeat
+                    total := total + 1;
+                    if total = 7 then goto 20
+                  until false
+                end;
+              total := -1;
+            20:
+              goto 30;
+              total := -2;
+            30:
+            End");
+
+        assert_eq!(value_of(&scope, "i"), "5");
+        assert_eq!(value_of(&scope, "k"), "1");
+        assert_eq!(value_of(&scope, "total"), "7");
+
+        assert!(matches!(
+            parse(
+                "
+                Label 10;
+                Var i : integer;
+                Begin
+                  goto 10;
+                  if true then begin 10: i := 1 end
+                End."
+            ),
+            Err(Error::InvalidGoto)
+        ));
+        assert!(matches!(
+            parse("Begin goto 10 End."),
+            Err(Error::LabelNotFound)
+        ));
+        assert!(matches!(
+            parse("Label 10; Begin goto 10 End."),
+            Err(Error::LabelNotFound)
+        ));
+        assert!(matches!(
+            parse("Label 10; Begin 10: ; 10: End."),
+            Err(Error::LabelRedefined)
+        ));
+    }
+
+    #[test]
+    fn test_with_statement() {
+        let scope = run("
+            Type Point = record x, y : integer end;
+                 Shape = record origin : Point; count : integer end;
+            Var points : array[1..3] of Point;
+                s : Shape;
+                x, calls : integer;
+
+            Function Next : integer;
+            Begin
+              calls := calls + 1;
+              Next := calls
+            End;
+
+            Procedure Bump(var v : integer);
+            Begin
+              v := v + 100
+            End;
+
+            Begin
+              calls := 0;
+              x := 7;
+              with points[Next] do
+                begin
+                  x := 10;
+                  y := x + 1
+                end;
+              with points[Next], s, origin do
+                begin
+                  y := 5;
+                  x := 3;
+                  count := 2;
+                  Inc(count);
+                  Bump(y)
+                end
+            End");
+
+        assert_eq!(value_of(&scope, "points"), "[[10, 11], [0, 0], [0, 0]]");
+        assert_eq!(value_of(&scope, "s"), "[[3, 105], 3]");
+        assert_eq!(value_of(&scope, "calls"), "2");
+        assert_eq!(value_of(&scope, "x"), "7");
+
+        assert!(matches!(
+            execute("Var i : integer; Begin with i do i := 1 End."),
+            Err(Error::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_control_flow_statements() {
+        let scope = run("
+            Var i, total, root, after : integer;
+
+            Function Find(n : integer) : integer;
+            Var k : integer;
+            Begin
+              Find := -1;
+              for k := 1 to 100 do
+                if k * k >= n then Exit(k)
+            End;
+
+            Procedure Count(n : integer);
+            Begin
+              if n > 3 then Exit;
+              total := total + 1000
+            End;
+
+            Begin
+              total := 0;
+              for i := 1 to 10 do
+                begin
+                  if i mod 2 = 0 then continue;
+                  if i > 7 then break;
+                  total := total + i
+                end;
+              i := 0;
+              repeat
+                i := i + 1;
+                if i < 3 then continue;
+                total := total + 100;
+                if i = 4 then break
+              until false;
+              Count(5);
+              root := Find(50);
+              Halt(3);
+              after := 1
+            End");
+
+        assert_eq!(value_of(&scope, "total"), "216");
+        assert_eq!(value_of(&scope, "root"), "8");
+        assert_eq!(value_of(&scope, "after"), "");
+        assert!(matches!(scope.borrow().signal(), Some(Signal::Halt(3))));
+
+        assert!(matches!(
+            parse("Begin break End."),
+            Err(Error::LoopRequired)
+        ));
+        assert!(matches!(
+            parse("Procedure P; Begin Exit(1) End; Begin End."),
+            Err(Error::InvalidSyntax)
+        ));
+
+        /* The predeclared routines are not reserved, variables and routines may take their names */
+        let scope = run("
+            Var exit, break, calls : integer;
+            Procedure Halt(code : integer);
+            Begin
+              calls := calls + code
+            End;
+            Begin
+              calls := 0;
+              exit := 1;
+              break := exit + 1;
+              Halt(5);
+              Halt(7)
+            End");
+
+        assert_eq!(value_of(&scope, "break"), "2");
+        assert_eq!(value_of(&scope, "calls"), "12");
+        assert!(scope.borrow().signal().is_none());
+    }
+
+    #[test]
+    fn test_halt_in_expression() {
+        /* The expression waiting for a function that halted the program is left without an error */
+        for statement in [
+            "x := 1 + Stop(4) * 2",
+            "x := -Stop(4)",
+            "if (Stop(4) > 0) and true then x := 1",
+            "while Stop(4) > 0 do x := 1",
+            "repeat x := x + 1 until Stop(4) > 0",
+            "for n := 1 to Stop(4) do x := 1",
+            "case Stop(4) of 1: x := 1 end",
+            "a[Stop(4)] := 1",
+            "x := a[Stop(4)]",
+            "WriteLn(Stop(4):3, 'x')",
+            "Inc(x, Stop(4))",
+            "Copy(Stop(4), x)",
+            "x := Ord(Stop(4) in [1, 2])",
+            "Halt(Stop(4))",
+        ] {
+            let code = format!(
+                "
+                Var x, n : integer;
+                    a : array[1..3] of integer;
+                Function Stop(code : integer) : integer;
+                Begin
+                  Halt(code);
+                  Stop := 1
+                End;
+                Procedure Copy(v : integer; Var w : integer);
+                Begin
+                  w := v
+                End;
+                Begin
+                  x := 0;
+                  {};
+                  WriteLn('after');
+                  x := 9
+                End",
+                statement
+            );
+            let block = match parse(&code) {
+                Ok(b) => b,
+                Err(e) => panic!("parse failed, error: {}", e),
+            };
+
+            let output = Rc::new(RefCell::new(Vec::new()));
+            let scope = Rc::new(RefCell::new(Scope::new("test", None, 0)));
+            scope.borrow_mut().set_console(Console::new(
+                Box::new(io::Cursor::new(Vec::new())),
+                Box::new(Output(output.clone())),
+            ));
+
+            assert!(block.visit(scope.clone()).is_ok(), "{}", statement);
+            assert!(matches!(scope.borrow().signal(), Some(Signal::Halt(4))));
+            assert_ne!(value_of(&scope, "x"), "9", "{}", statement);
+            assert!(output.borrow().is_empty(), "{}", statement);
+        }
+    }
 }